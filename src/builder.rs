@@ -0,0 +1,158 @@
+use crate::{CachePolicy, Endianness, OpenProcessError, DEFAULT_MAX_READ_SIZE};
+use std::io::{Error as IoError, ErrorKind};
+#[cfg(target_os = "windows")]
+use winapi::um::winnt::{
+    PROCESS_QUERY_INFORMATION, PROCESS_VM_OPERATION, PROCESS_VM_READ, PROCESS_VM_WRITE,
+};
+
+#[cfg(target_os = "linux")]
+type PlatformProcess = crate::LinuxProcess;
+#[cfg(target_os = "windows")]
+type PlatformProcess = crate::WindowsProcess;
+#[cfg(target_os = "macos")]
+type PlatformProcess = crate::MacosProcess;
+
+/// Options a process is opened with, obtained from `builder` of the platform process type or
+/// `ProcessBuilder::new`.
+///
+/// `Process` is a trait, so `Process::builder` can't name the type to build; `builder` lives on
+/// `LinuxProcess`, `WindowsProcess` and `MacosProcess` instead.
+///
+/// ```no_run
+/// use process_memory_reader::Endianness;
+/// # #[cfg(target_os = "linux")]
+/// use process_memory_reader::LinuxProcess as PlatformProcess;
+/// # #[cfg(target_os = "windows")]
+/// # use process_memory_reader::WindowsProcess as PlatformProcess;
+/// # #[cfg(target_os = "macos")]
+/// # use process_memory_reader::MacosProcess as PlatformProcess;
+///
+/// let process = PlatformProcess::builder(22212)
+///     .with_endianness(Endianness::Big)
+///     .with_pointer_size(4)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProcessBuilder {
+    pid: u32,
+    endianness: Endianness,
+    max_read_size: usize,
+    rate_limit: Option<u32>,
+    cache_policy: CachePolicy,
+    pointer_size: Option<usize>,
+    #[cfg(target_os = "linux")]
+    read_strategy: crate::ReadStrategy,
+    #[cfg(target_os = "windows")]
+    write_access: bool,
+}
+
+impl ProcessBuilder {
+    /// Starts configuring process with specified id, with the same defaults `open_process` uses.
+    pub fn new(pid: u32) -> Self {
+        ProcessBuilder {
+            pid,
+            endianness: Endianness::default(),
+            max_read_size: DEFAULT_MAX_READ_SIZE,
+            rate_limit: None,
+            cache_policy: CachePolicy::default(),
+            pointer_size: None,
+            #[cfg(target_os = "linux")]
+            read_strategy: crate::ReadStrategy::default(),
+            #[cfg(target_os = "windows")]
+            write_access: false,
+        }
+    }
+
+    /// Byte order the number readers decode with.
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Largest amount of bytes a single read may request.
+    pub fn with_max_read_size(mut self, max_read_size: usize) -> Self {
+        self.max_read_size = max_read_size;
+        self
+    }
+
+    /// Largest amount of reads made per second; reads sleep until they are allowed.
+    ///
+    /// 0 means no limit.
+    pub fn with_rate_limit(mut self, reads_per_second: u32) -> Self {
+        self.rate_limit = Some(reads_per_second);
+        self
+    }
+
+    /// How long the region list `is_plausible_pointer` checks against is cached.
+    pub fn with_cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = cache_policy;
+        self
+    }
+
+    /// Size of the pointers `read_ptr` and the pointer chains read, instead of the detected one.
+    ///
+    /// `build` fails with an `InvalidInput` error unless the size is 4 or 8.
+    pub fn with_pointer_size(mut self, pointer_size: usize) -> Self {
+        self.pointer_size = Some(pointer_size);
+        self
+    }
+
+    /// Mechanism memory is read with.
+    #[cfg(target_os = "linux")]
+    pub fn with_read_strategy(mut self, read_strategy: crate::ReadStrategy) -> Self {
+        self.read_strategy = read_strategy;
+        self
+    }
+
+    /// Opens the handle with `PROCESS_VM_WRITE | PROCESS_VM_OPERATION` as well, so the process
+    /// can be written.
+    #[cfg(target_os = "windows")]
+    pub fn with_write(mut self) -> Self {
+        self.write_access = true;
+        self
+    }
+
+    /// Opens the process and applies the options, telling why it could not be opened.
+    pub fn build(self) -> Result<PlatformProcess, OpenProcessError> {
+        #[cfg(target_os = "windows")]
+        let mut process = {
+            let mut access = PROCESS_VM_READ | PROCESS_QUERY_INFORMATION;
+
+            if self.write_access {
+                access |= PROCESS_VM_WRITE | PROCESS_VM_OPERATION;
+            }
+
+            crate::open_process_with_access_checked(self.pid, access)?
+        };
+        #[cfg(not(target_os = "windows"))]
+        let mut process = crate::open_process_checked(self.pid)?;
+
+        process.set_endianness(self.endianness);
+        process.set_max_read_size(self.max_read_size);
+        process.set_rate_limit(self.rate_limit);
+        process.set_cache_policy(self.cache_policy);
+        #[cfg(target_os = "linux")]
+        process.set_read_strategy(self.read_strategy);
+
+        if let Some(pointer_size) = self.pointer_size {
+            process.set_pointer_size(pointer_size).map_err(|_| {
+                IoError::new(ErrorKind::InvalidInput, "pointer size must be 4 or 8")
+            })?;
+        }
+
+        Ok(process)
+    }
+
+    /// Same as `build`, returning `None` if the process is not found or could not be opened.
+    pub fn open(self) -> Option<PlatformProcess> {
+        self.build().ok()
+    }
+}
+
+impl PlatformProcess {
+    /// Starts configuring process with specified id; see `ProcessBuilder`.
+    pub fn builder(pid: u32) -> ProcessBuilder {
+        ProcessBuilder::new(pid)
+    }
+}
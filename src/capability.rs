@@ -0,0 +1,38 @@
+/// Kind of access an operation on a process needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Read,
+    Write,
+    /// Changing the address space, needed alongside `Write` on Windows.
+    Operation,
+}
+
+/// Access granted for an opened process.
+///
+/// On Windows this follows the rights the handle was opened with; on Linux it tells which
+/// backends the kernel provides, while permission to use them is only checked by the access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Capabilities {
+    pub read: bool,
+    pub write: bool,
+    pub operation: bool,
+}
+
+impl Capabilities {
+    pub fn has(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Read => self.read,
+            Capability::Write => self.write,
+            Capability::Operation => self.operation,
+        }
+    }
+
+    /// First of `needed` that is not granted.
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    pub(crate) fn missing(&self, needed: &[Capability]) -> Option<Capability> {
+        needed
+            .iter()
+            .copied()
+            .find(|&capability| !self.has(capability))
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{check_read_size, offset_address, pod, read_pod, MemoryReadError, Pod, Process};
+use std::collections::HashSet;
+use std::mem::size_of;
+
+/// Offsets describing a chained hash table.
+///
+/// The table holds a pointer to an array of bucket pointers and the bucket count; every bucket
+/// points to a singly linked chain of nodes holding a key and a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashMapLayout {
+    /// Offset of the bucket array pointer within the table.
+    pub buckets_offset: usize,
+    /// Offset of the pointer-sized bucket count within the table.
+    pub bucket_count_offset: usize,
+    /// Offset of the next node pointer within a node.
+    pub next_offset: usize,
+    pub key_offset: usize,
+    pub value_offset: usize,
+    /// Most entries read before giving up on a corrupt table.
+    pub max_entries: usize,
+}
+
+/// Offsets describing a binary search tree node, such as libstdc++ `std::map` nodes.
+///
+/// Null child pointers mark leaves; the parent pointer is not needed for the traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RbTreeLayout {
+    pub left_offset: usize,
+    pub right_offset: usize,
+    pub key_offset: usize,
+    pub value_offset: usize,
+    /// Most nodes read before giving up on a corrupt tree.
+    pub max_nodes: usize,
+}
+
+/// Placement of the pointer and length fields of a span, such as a Rust slice or C# `Span`.
+///
+/// The default is a pointer followed by a pointer-sized length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanLayout {
+    /// Whether the length comes before the pointer, which is then aligned to pointer size.
+    pub length_first: bool,
+    /// Size of the unsigned length field (1 to 8 bytes), or `None` for pointer size.
+    pub length_size: Option<usize>,
+    /// Most elements read before treating the length as corrupt. The bytes are also checked
+    /// against `max_read_size`.
+    pub max_len: usize,
+}
+
+impl Default for SpanLayout {
+    fn default() -> Self {
+        SpanLayout {
+            length_first: false,
+            length_size: None,
+            max_len: usize::MAX,
+        }
+    }
+}
+
+pub(crate) fn read_span<P: Process + ?Sized, T: Pod>(
+    process: &P,
+    address: usize,
+    layout: &SpanLayout,
+) -> Result<Vec<T>, MemoryReadError> {
+    let pointer_size = process.pointer_size()?;
+    let length_size = layout.length_size.unwrap_or(pointer_size);
+
+    let (pointer_offset, length_offset) = if layout.length_first {
+        (length_size.next_multiple_of(pointer_size), 0)
+    } else {
+        (0, pointer_size)
+    };
+
+    let pointer = process.read_ptr(offset_address(address, pointer_offset)?)?;
+    let len =
+        process.read_int(offset_address(address, length_offset)?, length_size, false)? as usize;
+
+    if len > layout.max_len {
+        return Err(MemoryReadError::InvalidLength {
+            length: len,
+            max: layout.max_len,
+        });
+    }
+
+    check_read_size(len.saturating_mul(size_of::<T>()), process.max_read_size())?;
+
+    let mut values = vec![pod::zeroed::<T>(); len];
+
+    if len > 0 {
+        process.read_into_slice(pointer, &mut values)?;
+    }
+
+    Ok(values)
+}
+
+pub(crate) fn read_hashmap<P, K, V>(
+    process: &P,
+    address: usize,
+    layout: &HashMapLayout,
+) -> Result<Vec<(K, V)>, MemoryReadError>
+where
+    P: Process + ?Sized,
+    K: Pod,
+    V: Pod,
+{
+    let pointer_size = process.pointer_size()?;
+    let buckets = process.read_ptr(offset_address(address, layout.buckets_offset)?)?;
+    let bucket_count = process.read_ptr(offset_address(address, layout.bucket_count_offset)?)?;
+
+    check_read_size(
+        bucket_count.saturating_mul(pointer_size),
+        process.max_read_size(),
+    )?;
+
+    let mut entries = Vec::new();
+    let mut visited = HashSet::new();
+
+    for bucket in 0..bucket_count {
+        let mut node = process.read_ptr(offset_address(buckets, bucket * pointer_size)?)?;
+
+        // Nodes can be shared by chains, so stopping at visited ones also avoids duplicates.
+        while node != 0 && entries.len() < layout.max_entries && visited.insert(node) {
+            let key = read_pod(process, offset_address(node, layout.key_offset)?)?;
+            let value = read_pod(process, offset_address(node, layout.value_offset)?)?;
+            entries.push((key, value));
+
+            node = process.read_ptr(offset_address(node, layout.next_offset)?)?;
+        }
+    }
+
+    Ok(entries)
+}
+
+pub(crate) fn read_rbtree<P, K, V>(
+    process: &P,
+    root: usize,
+    layout: &RbTreeLayout,
+) -> Result<Vec<(K, V)>, MemoryReadError>
+where
+    P: Process + ?Sized,
+    K: Pod,
+    V: Pod,
+{
+    let mut entries = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = Vec::new();
+    let mut node = root;
+
+    loop {
+        // Links back to visited nodes are cycles in a corrupt tree and are treated as leaves.
+        while node != 0 && visited.len() < layout.max_nodes && visited.insert(node) {
+            stack.push(node);
+            node = process.read_ptr(offset_address(node, layout.left_offset)?)?;
+        }
+
+        node = match stack.pop() {
+            Some(node) => node,
+            None => break,
+        };
+
+        let key = read_pod(process, offset_address(node, layout.key_offset)?)?;
+        let value = read_pod(process, offset_address(node, layout.value_offset)?)?;
+        entries.push((key, value));
+
+        node = process.read_ptr(offset_address(node, layout.right_offset)?)?;
+    }
+
+    Ok(entries)
+}
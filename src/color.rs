@@ -0,0 +1,21 @@
+/// Order the channels of a packed 8-bit color are stored in, first byte first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorOrder {
+    #[default]
+    Rgba,
+    Argb,
+    Abgr,
+    Bgra,
+}
+
+impl ColorOrder {
+    /// Reorders stored channel bytes into `[r, g, b, a]`.
+    pub fn to_rgba(self, [c0, c1, c2, c3]: [u8; 4]) -> [u8; 4] {
+        match self {
+            ColorOrder::Rgba => [c0, c1, c2, c3],
+            ColorOrder::Argb => [c1, c2, c3, c0],
+            ColorOrder::Abgr => [c3, c2, c1, c0],
+            ColorOrder::Bgra => [c2, c1, c0, c3],
+        }
+    }
+}
@@ -0,0 +1,694 @@
+use crate::elf::{truncated, ElfFile, PT_LOAD};
+use crate::minidump::{self, MINIDUMP_SIGNATURE};
+use crate::{
+    check_read_size, Endianness, MemoryReadError, MemoryRegion, Module, Process, RegionKind,
+};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+const ET_CORE: u16 = 4;
+const PT_NOTE: u32 = 4;
+const PF_X: u32 = 1;
+const PF_W: u32 = 2;
+const PF_R: u32 = 4;
+/// Note listing the files mapped into the process (`"FILE"` in ASCII).
+const NT_FILE: u32 = 0x4649_4c45;
+/// Note holding the auxiliary vector the process was started with.
+const NT_AUXV: u32 = 6;
+/// Auxiliary vector key of the executable entry point.
+const AT_ENTRY: usize = 9;
+/// Size of the program header fields read, for 32-bit and 64-bit files.
+const PROGRAM_HEADER_SIZE_32: usize = 32;
+const PROGRAM_HEADER_SIZE_64: usize = 56;
+
+/// Loadable segment of the dump.
+#[derive(Debug, Clone)]
+pub(crate) struct Segment {
+    pub(crate) region: MemoryRegion,
+    pub(crate) file_offset: u64,
+    /// Bytes of the segment stored in the file; the rest was not dumped.
+    pub(crate) file_size: usize,
+}
+
+/// Saved ELF core dump or Windows minidump that can be read the same way as a live process.
+///
+/// Only bytes stored in the dump can be read. Kernels usually leave file-backed code segments
+/// out, and minidumps other than full memory ones hold only a few ranges, so reading the rest
+/// fails as if the memory were unmapped.
+#[derive(Debug)]
+pub struct CoreDumpProcess {
+    file: Mutex<File>,
+    segments: Vec<Segment>,
+    modules: Vec<Module>,
+    main_module_base: Option<usize>,
+    endianness: Endianness,
+    pointer_size: usize,
+}
+
+impl CoreDumpProcess {
+    /// Opens ELF core file or minidump and reads its segment and module lists.
+    ///
+    /// The format is told by the signature the file starts with.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, MemoryReadError> {
+        let mut file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+
+        if read_at(&mut file, file_len, 0, 4, truncated)?.as_slice() == MINIDUMP_SIGNATURE {
+            let minidump = minidump::parse(&mut file, file_len)?;
+
+            return Ok(CoreDumpProcess {
+                file: Mutex::new(file),
+                segments: minidump.segments,
+                modules: minidump.modules,
+                main_module_base: minidump.main_module_base,
+                endianness: Endianness::Little,
+                pointer_size: minidump.pointer_size,
+            });
+        }
+
+        // The identification and program header table fields fit in the 64-bit header.
+        let header = read_at(&mut file, file_len, 0, 64, truncated)?;
+
+        let elf = ElfFile::parse(&header)?;
+
+        if elf.u16(16)? != ET_CORE {
+            return Err(MemoryReadError::InvalidFormat {
+                reason: "ELF file is not a core dump",
+            });
+        }
+
+        let table_offset = elf.word(elf.offset(28, 32))?;
+        let entry_size = elf.u16(elf.offset(42, 54))? as usize;
+        let count = elf.u16(elf.offset(44, 56))? as usize;
+
+        if entry_size < elf.offset(PROGRAM_HEADER_SIZE_32, PROGRAM_HEADER_SIZE_64) {
+            return Err(MemoryReadError::InvalidFormat {
+                reason: "program header entries are too small",
+            });
+        }
+
+        let table = read_at(
+            &mut file,
+            file_len,
+            table_offset as u64,
+            entry_size * count,
+            truncated,
+        )?;
+        let table = elf.with_bytes(&table);
+        let mut segments = Vec::new();
+        let mut mappings = Vec::new();
+        let mut entry_point = None;
+
+        for index in 0..count {
+            let entry = index * entry_size;
+            let kind = table.u32(entry)?;
+            let file_offset = table.word(entry + table.offset(4, 8))? as u64;
+            let address = table.word(entry + table.offset(8, 16))?;
+            let file_size = table.word(entry + table.offset(16, 32))?;
+            let size = table.word(entry + table.offset(20, 40))?;
+            let flags = table.u32(entry + table.offset(24, 4))?;
+
+            match kind {
+                PT_LOAD => {
+                    let file_size = file_size.min(size);
+
+                    match file_offset.checked_add(file_size as u64) {
+                        Some(end) if end <= file_len => {}
+                        _ => return Err(truncated()),
+                    }
+
+                    segments.push(Segment {
+                        region: MemoryRegion {
+                            base: address,
+                            size,
+                            readable: flags & PF_R != 0,
+                            writable: flags & PF_W != 0,
+                            executable: flags & PF_X != 0,
+                            kind: RegionKind::Anonymous,
+                        },
+                        file_offset,
+                        file_size,
+                    })
+                }
+                PT_NOTE => {
+                    let notes = read_at(&mut file, file_len, file_offset, file_size, truncated)?;
+
+                    let notes = elf.with_bytes(&notes);
+
+                    mappings.extend(file_mappings(&notes)?);
+                    entry_point = entry_point.or(auxv_entry(&notes)?);
+                }
+                _ => {}
+            }
+        }
+
+        segments.sort_by_key(|segment| segment.region.base);
+
+        for segment in &mut segments {
+            let mapping = mappings
+                .iter()
+                .find(|(start, end, _)| (*start..*end).contains(&segment.region.base));
+
+            if let Some((_, _, path)) = mapping {
+                segment.region.kind = RegionKind::File(path.clone());
+            }
+        }
+
+        let modules = modules_from_mappings(&mappings);
+
+        Ok(CoreDumpProcess {
+            file: Mutex::new(file),
+            segments,
+            main_module_base: entry_point.and_then(|entry| {
+                modules
+                    .iter()
+                    .find(|module| module.contains(entry))
+                    .map(|module| module.base)
+            }),
+            modules,
+            endianness: elf.order,
+            pointer_size: if elf.is_64 { 8 } else { 4 },
+        })
+    }
+
+    fn find_segment(&self, address: usize) -> Option<&Segment> {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.region.base <= address);
+
+        self.segments[..index]
+            .last()
+            .filter(|segment| segment.region.contains(address))
+    }
+}
+
+/// Reads `len` bytes at `offset` of the file, rejecting ranges past its end with `truncated`
+/// before allocating them, as sizes of a corrupt dump can be anything.
+pub(crate) fn read_at(
+    file: &mut File,
+    file_len: u64,
+    offset: u64,
+    len: usize,
+    truncated: fn() -> MemoryReadError,
+) -> Result<Vec<u8>, MemoryReadError> {
+    match offset.checked_add(len as u64) {
+        Some(end) if end <= file_len => {}
+        _ => return Err(truncated()),
+    }
+
+    let mut bytes = vec![0u8; len];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Lists `(type, descriptor offset)` of the notes.
+fn note_descriptors(notes: &ElfFile) -> Result<Vec<(u32, usize)>, MemoryReadError> {
+    let align = |offset: usize| (offset + 3) & !3;
+    let mut descriptors = Vec::new();
+    let mut offset = 0;
+
+    while let Ok(name_size) = notes.u32(offset) {
+        let desc_size = notes.u32(offset + 4)? as usize;
+        let desc = offset + 12 + align(name_size as usize);
+
+        descriptors.push((notes.u32(offset + 8)?, desc));
+        offset = desc + align(desc_size);
+    }
+
+    Ok(descriptors)
+}
+
+/// Entry point of the executable from the `NT_AUXV` note, if there is one.
+fn auxv_entry(notes: &ElfFile) -> Result<Option<usize>, MemoryReadError> {
+    let word = if notes.is_64 { 8 } else { 4 };
+
+    for (kind, desc) in note_descriptors(notes)? {
+        if kind != NT_AUXV {
+            continue;
+        }
+
+        // `(key, value)` pairs, ending with a 0 key.
+        let mut entry = desc;
+
+        loop {
+            match notes.word(entry)? {
+                0 => break,
+                AT_ENTRY => return Ok(Some(notes.word(entry + word)?)),
+                _ => entry += 2 * word,
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads `(start, end, path)` entries of the `NT_FILE` notes.
+fn file_mappings(notes: &ElfFile) -> Result<Vec<(usize, usize, PathBuf)>, MemoryReadError> {
+    let mut mappings = Vec::new();
+
+    for (kind, desc) in note_descriptors(notes)? {
+        if kind == NT_FILE {
+            let word = if notes.is_64 { 8 } else { 4 };
+            let count = notes.word(desc)?;
+            let entries = desc + 2 * word;
+            let mut name = count
+                .checked_mul(3 * word)
+                .and_then(|size| size.checked_add(entries))
+                .ok_or_else(truncated)?;
+
+            for index in 0..count {
+                let entry = entries + index * 3 * word;
+                let path = notes.string(name)?;
+                name += path.len() + 1;
+
+                let (start, end) = (notes.word(entry)?, notes.word(entry + word)?);
+
+                if end < start {
+                    return Err(MemoryReadError::InvalidFormat {
+                        reason: "file mapping ends before it starts",
+                    });
+                }
+
+                mappings.push((start, end, PathBuf::from(path)));
+            }
+        }
+    }
+
+    Ok(mappings)
+}
+
+/// Merges mappings of the same file into modules sorted by base address.
+fn modules_from_mappings(mappings: &[(usize, usize, PathBuf)]) -> Vec<Module> {
+    let mut modules: Vec<Module> = Vec::new();
+
+    for (start, end, path) in mappings {
+        match modules.iter_mut().find(|module| module.path == *path) {
+            Some(module) => {
+                let module_end = module.end().max(*end);
+                module.base = module.base.min(*start);
+                module.size = module_end - module.base;
+            }
+            None => modules.push(Module {
+                name: path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned(),
+                path: path.clone(),
+                base: *start,
+                size: end - start,
+            }),
+        }
+    }
+
+    modules.sort_by_key(|module| module.base);
+    modules
+}
+
+impl Process for CoreDumpProcess {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        self.modules
+            .iter()
+            .find(|module| module.path.to_string_lossy().ends_with(module_name))
+            .map(|module| module.base)
+    }
+
+    /// Base of the module holding the entry point of ELF dumps, or of the first module listed
+    /// by minidumps, which is the executable.
+    fn main_module_base(&self) -> Option<usize> {
+        self.main_module_base
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        let read = self.read_bytes_partial(address, buffer)?;
+
+        if read == 0 && !buffer.is_empty() {
+            return Err(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: None,
+            });
+        }
+
+        if read != buffer.len() {
+            return Err(MemoryReadError::LessBytesRead {
+                expected: buffer.len(),
+                actual: read,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        check_read_size(buffer.len(), self.max_read_size())?;
+
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut read = 0;
+
+        // Adjacent segments are read one after another.
+        while read < buffer.len() {
+            let current = match address.checked_add(read) {
+                Some(current) => current,
+                None => break,
+            };
+            let segment = match self.find_segment(current) {
+                Some(segment) => segment,
+                None => break,
+            };
+
+            let offset = current - segment.region.base;
+
+            if offset >= segment.file_size {
+                break;
+            }
+
+            let len = (segment.file_size - offset).min(buffer.len() - read);
+            let position = segment
+                .file_offset
+                .checked_add(offset as u64)
+                .ok_or_else(truncated)?;
+            file.seek(SeekFrom::Start(position))?;
+            file.read_exact(&mut buffer[read..read + len])?;
+            read += len;
+        }
+
+        Ok(read)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        Ok(self
+            .segments
+            .iter()
+            .map(|segment| segment.region.clone())
+            .collect())
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        Ok(self.modules.clone())
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(self.pointer_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const LOAD_BASE: u64 = 0x1000;
+
+    /// Numbers the files of the tests running in parallel.
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    /// 64-bit little-endian core file with the program headers and the data following them.
+    fn core_file(entry_size: u16, headers: &[[u64; 6]], data: &[u8]) -> PathBuf {
+        let mut bytes = vec![0u8; 64];
+        bytes[..7].copy_from_slice(b"\x7fELF\x02\x01\x01");
+        bytes[16..18].copy_from_slice(&ET_CORE.to_le_bytes());
+        bytes[32..40].copy_from_slice(&64u64.to_le_bytes());
+        bytes[54..56].copy_from_slice(&entry_size.to_le_bytes());
+        bytes[56..58].copy_from_slice(&(headers.len() as u16).to_le_bytes());
+
+        // `[type, flags, offset, address, file size, memory size]`.
+        for &[kind, flags, offset, address, file_size, size] in headers {
+            let mut header = vec![0u8; entry_size as usize];
+            let fields = [
+                (0, kind as u32 as u64, 4),
+                (4, flags, 4),
+                (8, offset, 8),
+                (16, address, 8),
+                (32, file_size, 8),
+                (40, size, 8),
+            ];
+
+            for (at, value, len) in fields {
+                if at + len <= header.len() {
+                    header[at..at + len].copy_from_slice(&value.to_le_bytes()[..len]);
+                }
+            }
+
+            bytes.extend(header);
+        }
+
+        bytes.extend_from_slice(data);
+
+        temp_file(&bytes)
+    }
+
+    fn temp_file(bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "core-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    fn data_offset(headers: usize) -> u64 {
+        64 + 56 * headers as u64
+    }
+
+    /// Minidump of an x64 process with one module and one memory range holding `data`.
+    fn minidump_file(base: u64, data: &[u8]) -> PathBuf {
+        let name: Vec<u8> = "C:\\Games\\game.exe"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let system_info = 32 + 3 * 12;
+        let module_list = system_info + 4;
+        let name_rva = module_list + 4 + 108;
+        let memory_list = name_rva + 4 + name.len();
+        let data_rva = memory_list + 4 + 16;
+
+        let mut bytes = vec![0u8; data_rva];
+        bytes[..4].copy_from_slice(MINIDUMP_SIGNATURE);
+        bytes[8..12].copy_from_slice(&3u32.to_le_bytes());
+        bytes[12..16].copy_from_slice(&32u32.to_le_bytes());
+
+        let streams = [
+            (7u32, 4, system_info),
+            (4, name_rva - module_list, module_list),
+            (5, data_rva - memory_list, memory_list),
+        ];
+
+        for (index, (kind, size, rva)) in streams.iter().enumerate() {
+            let entry = 32 + index * 12;
+            bytes[entry..entry + 4].copy_from_slice(&kind.to_le_bytes());
+            bytes[entry + 4..entry + 8].copy_from_slice(&(*size as u32).to_le_bytes());
+            bytes[entry + 8..entry + 12].copy_from_slice(&(*rva as u32).to_le_bytes());
+        }
+
+        // `PROCESSOR_ARCHITECTURE_AMD64`.
+        bytes[system_info..system_info + 2].copy_from_slice(&9u16.to_le_bytes());
+
+        bytes[module_list..module_list + 4].copy_from_slice(&1u32.to_le_bytes());
+        let module = module_list + 4;
+        bytes[module..module + 8].copy_from_slice(&base.to_le_bytes());
+        bytes[module + 8..module + 12].copy_from_slice(&0x1000u32.to_le_bytes());
+        bytes[module + 20..module + 24].copy_from_slice(&(name_rva as u32).to_le_bytes());
+        bytes[name_rva..name_rva + 4].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        bytes[name_rva + 4..memory_list].copy_from_slice(&name);
+
+        bytes[memory_list..memory_list + 4].copy_from_slice(&1u32.to_le_bytes());
+        let range = memory_list + 4;
+        bytes[range..range + 8].copy_from_slice(&base.to_le_bytes());
+        bytes[range + 8..range + 12].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes[range + 12..range + 16].copy_from_slice(&(data_rva as u32).to_le_bytes());
+
+        bytes.extend_from_slice(data);
+
+        temp_file(&bytes)
+    }
+
+    #[test]
+    fn reads_loaded_segment() {
+        let data = 0x1234_5678u32.to_le_bytes();
+        let path = core_file(
+            56,
+            &[[
+                PT_LOAD as u64,
+                (PF_R | PF_W) as u64,
+                data_offset(1),
+                LOAD_BASE,
+                4,
+                0x1000,
+            ]],
+            &data,
+        );
+        let process = CoreDumpProcess::open(&path).unwrap();
+
+        assert_eq!(process.read_u32(LOAD_BASE as usize).unwrap(), 0x1234_5678);
+        // The rest of the segment was not dumped.
+        assert!(process.read_u32(LOAD_BASE as usize + 4).is_err());
+        assert_eq!(
+            process
+                .read_bytes_partial(usize::MAX - 1, &mut [0u8; 4])
+                .unwrap(),
+            0
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_too_small_program_headers() {
+        let path = core_file(16, &[[PT_LOAD as u64, 0, 0, LOAD_BASE, 0, 0x1000]], &[]);
+
+        assert!(matches!(
+            CoreDumpProcess::open(&path),
+            Err(MemoryReadError::InvalidFormat { .. })
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_segment_larger_than_file() {
+        let path = core_file(
+            56,
+            &[[PT_NOTE as u64, 0, data_offset(1), 0, 1 << 40, 0]],
+            &[],
+        );
+
+        assert!(matches!(
+            CoreDumpProcess::open(&path),
+            Err(MemoryReadError::InvalidFormat { .. })
+        ));
+
+        std::fs::remove_file(path).unwrap();
+
+        for offset in [data_offset(1), u64::MAX] {
+            let path = core_file(
+                56,
+                &[[PT_LOAD as u64, PF_R as u64, offset, LOAD_BASE, 8, 0x1000]],
+                &[0u8; 4],
+            );
+
+            assert!(matches!(
+                CoreDumpProcess::open(&path),
+                Err(MemoryReadError::InvalidFormat { .. })
+            ));
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_file_mapping_ending_before_start() {
+        // `NT_FILE` note with one mapping from 0x2000 down to 0x1000.
+        let mut note = Vec::new();
+        note.extend_from_slice(&5u32.to_le_bytes());
+        note.extend_from_slice(&(8u32 * 5 + 2).to_le_bytes());
+        note.extend_from_slice(&NT_FILE.to_le_bytes());
+        note.extend_from_slice(b"CORE\0\0\0\0");
+
+        for word in [1u64, 0x1000, 0x2000, 0x1000, 0] {
+            note.extend_from_slice(&word.to_le_bytes());
+        }
+
+        note.extend_from_slice(b"a\0\0\0");
+
+        let path = core_file(
+            56,
+            &[[PT_NOTE as u64, 0, data_offset(1), 0, note.len() as u64, 0]],
+            &note,
+        );
+
+        assert!(matches!(
+            CoreDumpProcess::open(&path),
+            Err(MemoryReadError::InvalidFormat {
+                reason: "file mapping ends before it starts"
+            })
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reads_minidump() {
+        let path = minidump_file(0x40_0000, &0xdead_beefu32.to_le_bytes());
+        let process = CoreDumpProcess::open(&path).unwrap();
+
+        assert_eq!(process.read_u32(0x40_0000).unwrap(), 0xdead_beef);
+        assert!(process.read_u32(0x40_0004).is_err());
+        assert_eq!(process.pointer_size().unwrap(), 8);
+        assert_eq!(process.base_address("game.exe"), Some(0x40_0000));
+        assert_eq!(process.main_module_base(), Some(0x40_0000));
+
+        let modules = process.modules().unwrap();
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0].name, "game.exe");
+        assert_eq!(modules[0].size, 0x1000);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_truncated_minidump() {
+        let path = minidump_file(0x40_0000, &[0u8; 16]);
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+
+        assert!(matches!(
+            CoreDumpProcess::open(&path),
+            Err(MemoryReadError::InvalidFormat {
+                reason: "truncated minidump"
+            })
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn finds_main_module_by_entry_point() {
+        // `NT_FILE` note mapping a library below the executable.
+        let mut note = Vec::new();
+        note.extend_from_slice(&5u32.to_le_bytes());
+        note.extend_from_slice(&(8u32 * 8 + 24).to_le_bytes());
+        note.extend_from_slice(&NT_FILE.to_le_bytes());
+        note.extend_from_slice(b"CORE\0\0\0\0");
+
+        for word in [2u64, 0x1000, 0x1000, 0x2000, 0, 0x40_0000, 0x40_1000, 0] {
+            note.extend_from_slice(&word.to_le_bytes());
+        }
+
+        note.extend_from_slice(b"/lib/libc.so\0/bin/game\0\0");
+
+        // `NT_AUXV` note with the entry point inside the executable.
+        note.extend_from_slice(&5u32.to_le_bytes());
+        note.extend_from_slice(&(8u32 * 4).to_le_bytes());
+        note.extend_from_slice(&NT_AUXV.to_le_bytes());
+        note.extend_from_slice(b"CORE\0\0\0\0");
+
+        for word in [AT_ENTRY as u64, 0x40_0123, 0, 0] {
+            note.extend_from_slice(&word.to_le_bytes());
+        }
+
+        let path = core_file(
+            56,
+            &[[PT_NOTE as u64, 0, data_offset(1), 0, note.len() as u64, 0]],
+            &note,
+        );
+        let process = CoreDumpProcess::open(&path).unwrap();
+
+        assert_eq!(process.modules().unwrap()[0].name, "libc.so");
+        assert_eq!(process.main_module_base(), Some(0x40_0000));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
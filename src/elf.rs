@@ -0,0 +1,198 @@
+use crate::{Endianness, MemoryReadError, PAGE_SIZE};
+use std::fs;
+use std::path::Path;
+
+pub(crate) const PT_LOAD: u32 = 1;
+const SHT_DYNSYM: u32 = 11;
+const SHN_UNDEF: u16 = 0;
+const SHN_ABS: u16 = 0xfff1;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+
+pub(crate) fn truncated() -> MemoryReadError {
+    MemoryReadError::InvalidFormat {
+        reason: "truncated ELF file",
+    }
+}
+
+/// Bounds-checked view of an ELF file of either class and byte order.
+pub(crate) struct ElfFile<'a> {
+    bytes: &'a [u8],
+    pub(crate) is_64: bool,
+    pub(crate) order: Endianness,
+}
+
+impl<'a> ElfFile<'a> {
+    /// Checks the ELF magic and reads class and byte order from the identification bytes.
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, MemoryReadError> {
+        if bytes.get(..4) != Some(b"\x7fELF") {
+            return Err(MemoryReadError::InvalidFormat {
+                reason: "missing ELF magic",
+            });
+        }
+
+        Ok(ElfFile {
+            bytes,
+            is_64: bytes.get(4) == Some(&2),
+            order: match bytes.get(5) {
+                Some(2) => Endianness::Big,
+                _ => Endianness::Little,
+            },
+        })
+    }
+
+    /// View of other bytes of the same file, such as a segment read separately.
+    pub(crate) fn with_bytes<'b>(&self, bytes: &'b [u8]) -> ElfFile<'b> {
+        ElfFile {
+            bytes,
+            is_64: self.is_64,
+            order: self.order,
+        }
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], MemoryReadError> {
+        let end = offset.checked_add(N).ok_or_else(truncated)?;
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.bytes.get(offset..end).ok_or_else(truncated)?);
+
+        Ok(bytes)
+    }
+
+    pub(crate) fn u8(&self, offset: usize) -> Result<u8, MemoryReadError> {
+        Ok(self.bytes::<1>(offset)?[0])
+    }
+
+    pub(crate) fn u16(&self, offset: usize) -> Result<u16, MemoryReadError> {
+        let bytes = self.bytes(offset)?;
+
+        Ok(match self.order {
+            Endianness::Little => u16::from_le_bytes(bytes),
+            Endianness::Big => u16::from_be_bytes(bytes),
+        })
+    }
+
+    pub(crate) fn u32(&self, offset: usize) -> Result<u32, MemoryReadError> {
+        let bytes = self.bytes(offset)?;
+
+        Ok(match self.order {
+            Endianness::Little => u32::from_le_bytes(bytes),
+            Endianness::Big => u32::from_be_bytes(bytes),
+        })
+    }
+
+    /// Reads address-sized field.
+    pub(crate) fn word(&self, offset: usize) -> Result<usize, MemoryReadError> {
+        if !self.is_64 {
+            return Ok(self.u32(offset)? as usize);
+        }
+
+        let bytes = self.bytes(offset)?;
+
+        Ok(match self.order {
+            Endianness::Little => u64::from_le_bytes(bytes),
+            Endianness::Big => u64::from_be_bytes(bytes),
+        } as usize)
+    }
+
+    /// Picks field offset of the file class.
+    pub(crate) fn offset(&self, offset_32: usize, offset_64: usize) -> usize {
+        if self.is_64 {
+            offset_64
+        } else {
+            offset_32
+        }
+    }
+
+    pub(crate) fn string(&self, offset: usize) -> Result<String, MemoryReadError> {
+        let bytes = self.bytes.get(offset..).ok_or_else(truncated)?;
+        let len = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or_else(truncated)?;
+
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+
+    /// Lowest page-aligned virtual address of the loadable segments.
+    fn load_base(&self) -> Result<usize, MemoryReadError> {
+        let offset = self.word(self.offset(28, 32))?;
+        let entry_size = self.u16(self.offset(42, 54))? as usize;
+        let count = self.u16(self.offset(44, 56))? as usize;
+        let mut load_base = None;
+
+        for index in 0..count {
+            let header = offset + index * entry_size;
+
+            if self.u32(header)? == PT_LOAD {
+                let address = self.word(header + self.offset(8, 16))? & !(PAGE_SIZE - 1);
+                load_base = Some(load_base.map_or(address, |base: usize| base.min(address)));
+            }
+        }
+
+        load_base.ok_or(MemoryReadError::InvalidFormat {
+            reason: "ELF file has no loadable segments",
+        })
+    }
+}
+
+/// Reads the lowest page-aligned `p_vaddr` of the loadable segments of the ELF file.
+pub(crate) fn preferred_base(path: &Path) -> Result<usize, MemoryReadError> {
+    let bytes = fs::read(path)?;
+
+    ElfFile::parse(&bytes)?.load_base()
+}
+
+/// Reads defined global functions and objects of the dynamic symbol table of the ELF file
+/// mapped at `base`, with their absolute addresses.
+pub(crate) fn exports(path: &Path, base: usize) -> Result<Vec<(String, usize)>, MemoryReadError> {
+    let bytes = fs::read(path)?;
+    let elf = ElfFile::parse(&bytes)?;
+
+    let bias = base.wrapping_sub(elf.load_base()?);
+    let section_offset = elf.word(elf.offset(32, 40))?;
+    let section_size = elf.u16(elf.offset(46, 58))? as usize;
+    let section_count = elf.u16(elf.offset(48, 60))? as usize;
+    let section = |index: usize| section_offset + index * section_size;
+
+    let mut exports = Vec::new();
+
+    for index in 0..section_count {
+        let header = section(index);
+
+        if elf.u32(header + 4)? != SHT_DYNSYM {
+            continue;
+        }
+
+        let offset = elf.word(header + elf.offset(16, 24))?;
+        let size = elf.word(header + elf.offset(20, 32))?;
+        let entry_size = elf.word(header + elf.offset(36, 56))?;
+        let link = elf.u32(header + elf.offset(24, 40))? as usize;
+        let strings = elf.word(section(link) + elf.offset(16, 24))?;
+
+        if entry_size == 0 {
+            return Err(truncated());
+        }
+
+        for symbol in (offset..offset + size).step_by(entry_size) {
+            let info = elf.u8(symbol + elf.offset(12, 4))?;
+            let section_index = elf.u16(symbol + elf.offset(14, 6))?;
+            let value = elf.word(symbol + elf.offset(4, 8))?;
+            let (binding, kind) = (info >> 4, info & 0xf);
+
+            // Absolute symbols, such as symbol version names, are not addresses in the module.
+            if matches!(section_index, SHN_UNDEF | SHN_ABS)
+                || !matches!(binding, STB_GLOBAL | STB_WEAK)
+                || !matches!(kind, STT_FUNC | STT_OBJECT)
+            {
+                continue;
+            }
+
+            let name = elf.string(strings + elf.u32(symbol)? as usize)?;
+            exports.push((name, bias.wrapping_add(value)));
+        }
+    }
+
+    Ok(exports)
+}
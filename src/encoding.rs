@@ -0,0 +1,12 @@
+/// Text encoding of a null-terminated string read by `Process::read_cstring`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StringEncoding {
+    /// Invalid sequences are replaced with `U+FFFD`.
+    #[default]
+    Utf8,
+    /// Ends at a null code unit; unpaired surrogates are replaced with `U+FFFD`.
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, mapping every byte to the code point of the same value.
+    Latin1,
+}
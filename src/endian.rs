@@ -0,0 +1,7 @@
+/// Byte order of numbers in the target's memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
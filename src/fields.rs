@@ -0,0 +1,152 @@
+use crate::{pointer_from_bytes, Endianness};
+
+/// Type of a field read by `Process::read_struct_fields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldKind {
+    U32,
+    F32,
+    /// Pointer sized for the target process.
+    Ptr,
+    /// Inline null-terminated string of at most `max_len` bytes.
+    CString {
+        max_len: usize,
+    },
+}
+
+impl FieldKind {
+    /// Size of the field in bytes.
+    pub(crate) fn size(&self, pointer_size: usize) -> usize {
+        match *self {
+            FieldKind::U32 | FieldKind::F32 => 4,
+            FieldKind::Ptr => pointer_size,
+            FieldKind::CString { max_len } => max_len,
+        }
+    }
+
+    /// Decodes the field from bytes starting at its offset.
+    pub(crate) fn decode(
+        &self,
+        bytes: &[u8],
+        pointer_size: usize,
+        order: Endianness,
+    ) -> FieldValue {
+        let bytes = &bytes[..self.size(pointer_size)];
+
+        match *self {
+            FieldKind::U32 => FieldValue::U32(u32_from_bytes(bytes, order)),
+            FieldKind::F32 => FieldValue::F32(f32::from_bits(u32_from_bytes(bytes, order))),
+            FieldKind::Ptr => FieldValue::Ptr(pointer_from_bytes(bytes, order)),
+            FieldKind::CString { .. } => {
+                let len = bytes
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .unwrap_or(bytes.len());
+
+                FieldValue::CString(String::from_utf8_lossy(&bytes[..len]).into_owned())
+            }
+        }
+    }
+}
+
+fn u32_from_bytes(bytes: &[u8], order: Endianness) -> u32 {
+    let word = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+    match order {
+        Endianness::Little => u32::from_le_bytes(word),
+        Endianness::Big => u32::from_be_bytes(word),
+    }
+}
+
+/// Field of a struct at `offset` bytes from its base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    pub offset: usize,
+    pub kind: FieldKind,
+}
+
+impl FieldSpec {
+    pub fn new(offset: usize, kind: FieldKind) -> Self {
+        FieldSpec { offset, kind }
+    }
+}
+
+/// Decoded value of a field.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldValue {
+    U32(u32),
+    F32(f32),
+    Ptr(usize),
+    CString(String),
+}
+
+/// Named field of a `Layout`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutField {
+    pub name: String,
+    pub offset: usize,
+    pub kind: FieldKind,
+}
+
+/// Struct layout described at runtime, e.g. loaded from an offsets file or built in code.
+///
+/// ```no_run
+/// use process_memory_reader::{Layout, Process};
+///
+/// let entity = Layout::new()
+///     .u32("health", 0x10)
+///     .f32("x", 0x40)
+///     .cstring("name", 0x80, 64)
+///     .ptr("target", 0xa0);
+///
+/// let process = process_memory_reader::open_process(22212).unwrap();
+/// let fields = process.read_described(0x7ff6_1234_0000, &entity).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    pub fields: Vec<LayoutField>,
+}
+
+impl Layout {
+    pub fn new() -> Self {
+        Layout::default()
+    }
+
+    /// Appends field of the kind at `offset`.
+    pub fn field(mut self, name: &str, offset: usize, kind: FieldKind) -> Self {
+        self.fields.push(LayoutField {
+            name: name.to_owned(),
+            offset,
+            kind,
+        });
+        self
+    }
+
+    pub fn u32(self, name: &str, offset: usize) -> Self {
+        self.field(name, offset, FieldKind::U32)
+    }
+
+    pub fn f32(self, name: &str, offset: usize) -> Self {
+        self.field(name, offset, FieldKind::F32)
+    }
+
+    pub fn ptr(self, name: &str, offset: usize) -> Self {
+        self.field(name, offset, FieldKind::Ptr)
+    }
+
+    /// Appends inline null-terminated string of at most `max_len` bytes.
+    pub fn cstring(self, name: &str, offset: usize, max_len: usize) -> Self {
+        self.field(name, offset, FieldKind::CString { max_len })
+    }
+
+    /// Offsets and kinds of the fields, in declaration order.
+    pub(crate) fn specs(&self) -> Vec<FieldSpec> {
+        self.fields
+            .iter()
+            .map(|field| FieldSpec::new(field.offset, field.kind))
+            .collect()
+    }
+}
@@ -0,0 +1,33 @@
+/// Order bits are numbered in within each byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitOrder {
+    /// Bit 0 is the least significant bit.
+    #[default]
+    LsbFirst,
+    /// Bit 0 is the most significant bit.
+    MsbFirst,
+}
+
+/// Packed byte of boolean flags, with bit 0 being the least significant bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Flags(pub u8);
+
+impl Flags {
+    /// Checks whether bit `bit` (0 to 7) is set.
+    pub fn is_set(&self, bit: u8) -> bool {
+        bit < 8 && self.0 & (1 << bit) != 0
+    }
+
+    /// Indices of set bits in ascending order.
+    pub fn iter_set(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+
+        (0..8).filter(move |bit| bits & (1 << bit) != 0)
+    }
+}
+
+impl From<u8> for Flags {
+    fn from(bits: u8) -> Self {
+        Flags(bits)
+    }
+}
@@ -0,0 +1,21 @@
+/// Formats GUID bytes as read by `Process::read_guid` in the canonical braced form.
+///
+/// The first three fields are stored little-endian and the last two as plain bytes, so
+/// `{00112233-4455-6677-8899-AABBCCDDEEFF}` is stored as `33 22 11 00 55 44 77 66 88 99 AA ..`.
+pub fn format_guid(guid: &[u8; 16]) -> String {
+    let data1 = u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]);
+    let data2 = u16::from_le_bytes([guid[4], guid[5]]);
+    let data3 = u16::from_le_bytes([guid[6], guid[7]]);
+
+    let mut formatted = format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-",
+        data1, data2, data3, guid[8], guid[9]
+    );
+
+    for byte in &guid[10..] {
+        formatted.push_str(&format!("{:02X}", byte));
+    }
+
+    formatted.push('}');
+    formatted
+}
@@ -0,0 +1,154 @@
+use std::io::Error as IoError;
+
+/// Memory footprint of the target process, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    /// Physical memory currently in use (`VmRSS` on Linux).
+    pub working_set: usize,
+    /// Highest working set seen so far (`VmHWM` on Linux).
+    pub peak_working_set: usize,
+    /// Memory not shared with other processes (`RssAnon` on Linux).
+    pub private_bytes: usize,
+}
+
+/// Scheduling state of a process, from the state letter Linux reports in `/proc/<pid>/stat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessState {
+    Running,
+    Sleeping,
+    /// Uninterruptible sleep, usually waiting for I/O.
+    DiskSleep,
+    Stopped,
+    TracingStop,
+    /// Exited, but not yet reaped by its parent. Its memory can no longer be read.
+    Zombie,
+    Dead,
+    Idle,
+    /// State letter this crate does not know.
+    Other(char),
+}
+
+impl From<char> for ProcessState {
+    fn from(letter: char) -> Self {
+        match letter {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'Z' => ProcessState::Zombie,
+            'X' | 'x' => ProcessState::Dead,
+            'I' => ProcessState::Idle,
+            other => ProcessState::Other(other),
+        }
+    }
+}
+
+/// Fields of `/proc/<pid>/status` on Linux, with the closest equivalents on Windows.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessStatus {
+    /// Command name on Linux, executable file name on Windows.
+    pub name: String,
+    /// Linux only.
+    pub state: Option<ProcessState>,
+    pub ppid: u32,
+    /// Real user id (Linux only).
+    pub uid: Option<u32>,
+    /// Real group id (Linux only).
+    pub gid: Option<u32>,
+    /// Resident memory in bytes, the working set on Windows.
+    pub vm_rss: usize,
+    /// Virtual memory in bytes, the committed pagefile usage on Windows.
+    pub vm_size: usize,
+    pub threads: usize,
+}
+
+/// Instruction set the target process runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Architecture {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+}
+
+impl Architecture {
+    /// Size in bytes of a pointer of the instruction set.
+    pub fn pointer_size(self) -> usize {
+        match self {
+            Architecture::X86 | Architecture::Arm => 4,
+            Architecture::X86_64 | Architecture::Aarch64 => 8,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+type PlatformProcess = crate::LinuxProcess;
+#[cfg(target_os = "windows")]
+type PlatformProcess = crate::WindowsProcess;
+#[cfg(target_os = "macos")]
+type PlatformProcess = crate::MacosProcess;
+
+/// Process listed by a `ProcessSnapshot`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    /// Executable file name, such as `game.exe`.
+    pub name: String,
+}
+
+/// Lists running processes with their ids and executable names.
+pub fn enumerate_processes() -> Result<Vec<ProcessEntry>, IoError> {
+    crate::process_entries()
+}
+
+/// Finds processes whose name contains `name`, ignoring ASCII case, skipping those that can't
+/// be opened.
+pub fn find_by_name_contains(name: &str) -> Result<Vec<PlatformProcess>, IoError> {
+    let name = name.to_ascii_lowercase();
+
+    Ok(enumerate_processes()?
+        .into_iter()
+        .filter(|entry| entry.name.to_ascii_lowercase().contains(&name))
+        .filter_map(|entry| crate::open_process(entry.pid))
+        .collect())
+}
+
+/// Process list captured once, so several names can be looked up against the same view.
+///
+/// Taken with a single toolhelp snapshot on Windows, a single `/proc` listing on Linux and a
+/// single `proc_listallpids` call on macOS.
+#[derive(Debug, Clone)]
+pub struct ProcessSnapshot {
+    entries: Vec<ProcessEntry>,
+}
+
+impl ProcessSnapshot {
+    /// Captures the processes running right now.
+    pub fn new() -> Result<Self, IoError> {
+        Ok(ProcessSnapshot {
+            entries: crate::process_entries()?,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ProcessEntry> {
+        self.entries.iter()
+    }
+
+    /// Opens the first process with exactly matching name that can be opened.
+    pub fn find(&self, name: &str) -> Option<PlatformProcess> {
+        self.matching(name)
+            .find_map(|entry| crate::open_process(entry.pid))
+    }
+
+    /// Opens all processes with exactly matching name that can be opened.
+    pub fn find_all(&self, name: &str) -> Vec<PlatformProcess> {
+        self.matching(name)
+            .filter_map(|entry| crate::open_process(entry.pid))
+            .collect()
+    }
+
+    fn matching<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ProcessEntry> {
+        self.entries.iter().filter(move |entry| entry.name == name)
+    }
+}
@@ -9,6 +9,35 @@
 //!
 //! process.read_u8(base_address + 0x127).unwrap();
 //! ```
+//!
+//! # Byte order
+//! Number readers decode with the process [`Endianness`](Process::endianness), whatever the
+//! host byte order is. Values read through [`Pod`] are raw bytes reinterpreted on the host and
+//! then converted with [`Pod::to_host_order`], which swaps numbers but leaves structs untouched.
+//!
+//! # Features
+//! - `bitflags`: adds `Process::read_bitflags` for [`bitflags`](https://docs.rs/bitflags) types.
+//! - `encoding_rs`: adds `Process::read_string_encoded` for
+//!   [`encoding_rs`](https://docs.rs/encoding_rs) encodings.
+//! - `glam`: adds `Process::read_glam_vec2`, `read_glam_vec3` and `read_glam_vec4`.
+//! - `metrics`: adds `stats` to the platform processes, counting reads, bytes and failures.
+//! - `serde`: derives `Serialize` and `Deserialize` for `Layout` and the field types.
+//! - `tracing`: emits [`tracing`](https://docs.rs/tracing) events for process open/close,
+//!   module resolution and every `read_bytes` call.
+//! - `user32`: adds Windows `find_by_window_title`.
+
+/// Emits a `tracing` event when the `tracing` feature is enabled, otherwise expands to nothing.
+#[cfg(feature = "tracing")]
+macro_rules! trace_event (
+    ($level: ident, $($arg: tt)*) => (
+        tracing::$level!($($arg)*)
+    );
+);
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event (
+    ($level: ident, $($arg: tt)*) => ();
+);
 
 #[cfg(target_os = "windows")]
 pub mod windows;
@@ -20,14 +49,220 @@ pub mod linux;
 #[cfg(target_os = "linux")]
 pub use linux::*;
 
-use std::io::Error as IoError;
+#[cfg(target_os = "macos")]
+pub mod macos;
+#[cfg(target_os = "macos")]
+pub use macos::*;
+
+mod builder;
+pub use builder::ProcessBuilder;
+
+mod capability;
+pub use capability::{Capabilities, Capability};
+
+mod collections;
+pub use collections::{HashMapLayout, RbTreeLayout, SpanLayout};
+
+mod color;
+pub use color::ColorOrder;
+
+mod core_dump;
+pub use core_dump::CoreDumpProcess;
+
+mod elf;
+
+mod encoding;
+pub use encoding::StringEncoding;
+
+mod endian;
+pub use endian::Endianness;
+
+mod fields;
+pub use fields::{FieldKind, FieldSpec, FieldValue, Layout, LayoutField};
+
+mod flags;
+pub use flags::{BitOrder, Flags};
+
+mod guid;
+pub use guid::format_guid;
+
+mod info;
+pub use info::{
+    enumerate_processes, find_by_name_contains, Architecture, MemoryUsage, ProcessEntry,
+    ProcessSnapshot, ProcessState, ProcessStatus,
+};
+
+#[cfg(any(test, feature = "mock"))]
+mod mock;
+#[cfg(feature = "mock")]
+pub use mock::MockProcess;
+
+mod minidump;
+
+mod module;
+pub use module::{LoadedModule, Module, ModuleExports, ResolvedPointer};
+
+#[cfg(any(test, target_os = "macos"))]
+mod nsstring;
+
+mod pe;
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+mod once;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+pub use once::{
+    read_once_bytes, read_once_f32, read_once_f64, read_once_i32, read_once_i64, read_once_u32,
+    read_once_u64, read_once_u8,
+};
+
+mod pod;
+pub use pod::Pod;
+
+mod pointer_map;
+pub use pointer_map::{PointerMap, PointerPath};
+
+mod rebase;
+pub use rebase::Rebased;
+
+mod records;
+pub use records::Records;
+
+mod region;
+pub use region::{CachePolicy, MemoryRegion, RegionKind};
+
+mod scan;
+pub use scan::parse_pattern;
+
+mod snapshot;
+pub use snapshot::MemorySnapshot;
+
+#[cfg(feature = "metrics")]
+mod stats;
+#[cfg(feature = "metrics")]
+pub use stats::ReadStats;
+
+mod throttle;
+
+mod view;
+pub use view::RegionView;
+
+mod watch;
+pub use watch::{Change, Watcher};
+
+#[cfg(test)]
+mod tests;
+
+use scan::SCAN_CHUNK_SIZE;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufWriter, Error as IoError, Write};
+use std::mem::{size_of, MaybeUninit};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Page size assumed when the real one is not known.
+pub(crate) const PAGE_SIZE: usize = 4096;
+
+/// Interval `wait_for_module` polls at.
+const MODULE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How many times the requested amount of reads `read_u64_stable` makes at most.
+const STABLE_READ_ATTEMPTS: usize = 4;
+
+/// Rounds `read_u64_split_consistent` makes before settling for the last pair of words.
+const SPLIT_READ_ATTEMPTS: usize = 16;
+
+/// Largest span `read_and_parse` reads into a stack buffer instead of allocating.
+const STACK_BUFFER_SIZE: usize = 512;
+
+/// Longest string `read_string` reads.
+const MAX_STRING_LEN: usize = 4096;
+
+/// Bytes per read of strings whose length is not known.
+const STRING_CHUNK_SIZE: usize = 256;
+
+/// Default limit for the size of a single read.
+pub const DEFAULT_MAX_READ_SIZE: usize = 64 * 1024 * 1024;
 
 /// Errors that can be caught when trying to read process memory.
 #[derive(Debug)]
 pub enum MemoryReadError {
-    InaccessibleMemoryAddress { address: usize },
-    LessBytesRead { expected: usize, actual: usize },
-    IOError { io_error: IoError },
+    InaccessibleMemoryAddress {
+        address: usize,
+        /// Error reported by the OS for the failed read, if any.
+        os_error: Option<IoError>,
+    },
+    LessBytesRead {
+        expected: usize,
+        actual: usize,
+    },
+    IOError {
+        io_error: IoError,
+    },
+    ReadTooLarge {
+        requested: usize,
+        max: usize,
+    },
+    InvalidLength {
+        length: usize,
+        max: usize,
+    },
+    InvalidEnum {
+        value: u32,
+    },
+    Timeout {
+        timeout: Duration,
+    },
+    /// Null pointer was read at the address.
+    NullPointer {
+        address: usize,
+    },
+    ModuleNotFound {
+        name: String,
+    },
+    ExportNotFound {
+        name: String,
+    },
+    /// No process with the id could be opened.
+    ProcessNotFound {
+        pid: u32,
+    },
+    /// Pid of the opened process now belongs to another process.
+    ProcessChanged {
+        pid: u32,
+    },
+    /// Adding the offset to the address would wrap around the address space.
+    AddressOverflow {
+        address: usize,
+        offset: usize,
+    },
+    /// String bytes are not valid UTF-8.
+    InvalidUtf8 {
+        bytes: Vec<u8>,
+    },
+    /// End of the address range is below its start.
+    InvalidRange {
+        start: usize,
+        end: usize,
+    },
+    /// Module image could not be parsed.
+    InvalidFormat {
+        reason: &'static str,
+    },
+    /// Bytes at the address differ from the expected signature.
+    MagicMismatch {
+        address: usize,
+        expected: Vec<u8>,
+        actual: Vec<u8>,
+    },
+    /// Request at `index` of a `read_many` batch failed.
+    RequestFailed {
+        index: usize,
+        read_error: Box<MemoryReadError>,
+    },
 }
 
 impl From<IoError> for MemoryReadError {
@@ -36,59 +271,2030 @@ impl From<IoError> for MemoryReadError {
     }
 }
 
+/// Errors that can be caught when trying to open a process.
+#[derive(Debug)]
+pub enum OpenProcessError {
+    /// No process with the id exists.
+    NotFound {
+        pid: u32,
+    },
+    /// Process exists, but this process is not allowed to open it, such as a process of another
+    /// user or an elevated one.
+    AccessDenied {
+        pid: u32,
+    },
+    IOError {
+        io_error: IoError,
+    },
+}
+
+impl OpenProcessError {
+    /// Classifies the error the OS reported for opening process `pid`.
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    pub(crate) fn from_io_error(pid: u32, io_error: IoError) -> Self {
+        match io_error.kind() {
+            std::io::ErrorKind::NotFound => OpenProcessError::NotFound { pid },
+            std::io::ErrorKind::PermissionDenied => OpenProcessError::AccessDenied { pid },
+            _ => OpenProcessError::IOError { io_error },
+        }
+    }
+}
+
+impl From<IoError> for OpenProcessError {
+    fn from(io_error: IoError) -> Self {
+        OpenProcessError::IOError { io_error }
+    }
+}
+
+/// Errors that can be caught when trying to write process memory.
+#[derive(Debug)]
+pub enum MemoryWriteError {
+    InaccessibleMemoryAddress {
+        address: usize,
+        /// Error reported by the OS for the failed write, if any.
+        os_error: Option<IoError>,
+    },
+    LessBytesWritten {
+        expected: usize,
+        actual: usize,
+    },
+    IOError {
+        io_error: IoError,
+    },
+    /// Process was opened without the access the write needs.
+    ///
+    /// On Windows, open the process with `open_process_with_access` and
+    /// `PROCESS_VM_WRITE | PROCESS_VM_OPERATION`.
+    MissingCapability {
+        needed: Capability,
+    },
+    /// Reading the bytes to write failed.
+    ReadError {
+        read_error: MemoryReadError,
+    },
+}
+
+impl From<IoError> for MemoryWriteError {
+    fn from(io_error: IoError) -> Self {
+        MemoryWriteError::IOError { io_error }
+    }
+}
+
+impl From<MemoryReadError> for MemoryWriteError {
+    fn from(read_error: MemoryReadError) -> Self {
+        MemoryWriteError::ReadError { read_error }
+    }
+}
+
+/// Turns failures caused by unmapped memory into `None`.
+fn unmapped_as_none<T>(result: Result<T, MemoryReadError>) -> Result<Option<T>, MemoryReadError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(
+            MemoryReadError::InaccessibleMemoryAddress { .. }
+            | MemoryReadError::LessBytesRead { .. },
+        ) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Rejects reads bigger than `max` before anything is allocated or requested.
+pub(crate) fn check_read_size(requested: usize, max: usize) -> Result<(), MemoryReadError> {
+    if requested > max {
+        return Err(MemoryReadError::ReadTooLarge { requested, max });
+    }
+
+    Ok(())
+}
+
+/// Rejects pointer sizes other than the 4 and 8 bytes the pointer readers decode.
+pub(crate) fn check_pointer_size(pointer_size: usize) -> Result<(), MemoryReadError> {
+    if pointer_size != 4 && pointer_size != 8 {
+        return Err(MemoryReadError::InvalidFormat {
+            reason: "pointer size must be 4 or 8",
+        });
+    }
+
+    Ok(())
+}
+
+/// Views initialized bytes as a buffer the OS reads into.
+pub(crate) fn as_uninit_mut(buffer: &mut [u8]) -> &mut [MaybeUninit<u8>] {
+    // Only initialized bytes are ever written through the returned view.
+    unsafe { &mut *(buffer as *mut [u8] as *mut [MaybeUninit<u8>]) }
+}
+
+/// Views buffer whose bytes were all written as initialized bytes.
+///
+/// # Safety
+///
+/// Every byte of `buffer` must be initialized.
+pub(crate) unsafe fn assume_init_mut(buffer: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    &mut *(buffer as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
+/// Decodes a pointer of `bytes.len()` size.
+pub(crate) fn pointer_from_bytes(bytes: &[u8], order: Endianness) -> usize {
+    match *bytes {
+        [a, b, c, d] => match order {
+            Endianness::Little => u32::from_le_bytes([a, b, c, d]) as usize,
+            Endianness::Big => u32::from_be_bytes([a, b, c, d]) as usize,
+        },
+        [a, b, c, d, e, f, g, h] => match order {
+            Endianness::Little => u64::from_le_bytes([a, b, c, d, e, f, g, h]) as usize,
+            Endianness::Big => u64::from_be_bytes([a, b, c, d, e, f, g, h]) as usize,
+        },
+        _ => unreachable!("unsupported pointer size {}", bytes.len()),
+    }
+}
+
+/// Reads `N` consecutive `f32` values with a single read.
+fn read_f32_array<P: Process + ?Sized, const N: usize>(
+    process: &P,
+    address: usize,
+) -> Result<[f32; N], MemoryReadError> {
+    let mut words = [[0u8; 4]; N];
+    process.read_into_slice(address, &mut words)?;
+
+    let order = process.endianness();
+
+    Ok(words.map(|word| match order {
+        Endianness::Little => f32::from_le_bytes(word),
+        Endianness::Big => f32::from_be_bytes(word),
+    }))
+}
+
+/// Reads single `Pod` value as laid out in memory.
+pub(crate) fn read_pod<P: Process + ?Sized, T: Pod>(
+    process: &P,
+    address: usize,
+) -> Result<T, MemoryReadError> {
+    let mut value = [pod::zeroed::<T>()];
+    process.read_into_slice(address, &mut value)?;
+
+    Ok(value[0])
+}
+
+/// Decodes UTF-16 code units stored in `bytes`, replacing invalid ones with `U+FFFD`.
+pub(crate) fn decode_utf16(bytes: &[u8], order: Endianness) -> String {
+    let units = bytes.chunks_exact(2).map(|unit| match order {
+        Endianness::Little => u16::from_le_bytes([unit[0], unit[1]]),
+        Endianness::Big => u16::from_be_bytes([unit[0], unit[1]]),
+    });
+
+    char::decode_utf16(units)
+        .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Reads at most `max` bytes until null char, `chunk` bytes per read.
+///
+/// Reads never cross a page boundary, and a failed read is retried with a halved chunk. With
+/// `stop_at_unreadable` the bytes read before unmapped memory are returned instead of the error.
+fn read_until_null<P: Process + ?Sized>(
+    process: &P,
+    address: usize,
+    chunk: usize,
+    max: usize,
+    stop_at_unreadable: bool,
+) -> Result<Vec<u8>, MemoryReadError> {
+    read_until_null_unit(process, address, 1, chunk, max, stop_at_unreadable)
+}
+
+/// Same as `read_until_null`, but the string ends at a null code unit of `unit` bytes, such as
+/// the 2 byte one of UTF-16.
+///
+/// Only whole units are returned, so `max` is rounded down to a multiple of `unit`. A unit
+/// straddling a page boundary is read at once.
+fn read_until_null_unit<P: Process + ?Sized>(
+    process: &P,
+    address: usize,
+    unit: usize,
+    chunk: usize,
+    max: usize,
+    stop_at_unreadable: bool,
+) -> Result<Vec<u8>, MemoryReadError> {
+    let chunk = chunk.max(1);
+    check_read_size(chunk.min(max), process.max_read_size())?;
+
+    let max = max - max % unit;
+    let mut bytes = Vec::new();
+    let mut buffer = vec![0u8; chunk.max(unit).min(max)];
+    let mut chunk = chunk;
+    let page_size = process.page_size().max(1);
+
+    while bytes.len() < max {
+        let current = offset_address(address, bytes.len())?;
+        let len = chunk
+            .min(page_size - current % page_size)
+            .min(max - bytes.len());
+        let len = match len - len % unit {
+            0 => unit,
+            len => len,
+        };
+        let buffer = &mut buffer[..len];
+
+        if let Err(error) = process.read_bytes(current, buffer) {
+            if len == unit {
+                return match error {
+                    MemoryReadError::InaccessibleMemoryAddress { .. }
+                    | MemoryReadError::LessBytesRead { .. }
+                        if stop_at_unreadable && !bytes.is_empty() =>
+                    {
+                        Ok(bytes)
+                    }
+                    error => Err(error),
+                };
+            }
+
+            chunk = len / 2;
+            continue;
+        }
+
+        match buffer
+            .chunks_exact(unit)
+            .position(|unit| unit.iter().all(|&byte| byte == 0))
+        {
+            Some(end) => {
+                bytes.extend_from_slice(&buffer[..end * unit]);
+                break;
+            }
+            None => bytes.extend_from_slice(buffer),
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Reads the `{ptr, cap, len}` header shared by Rust `Vec` and `String`.
+fn read_rust_vec_parts<P: Process + ?Sized>(
+    process: &P,
+    address: usize,
+) -> Result<(usize, usize, usize), MemoryReadError> {
+    let pointer_size = process.pointer_size()?;
+    let mut buffer = [0u8; 3 * 8];
+    let buffer = &mut buffer[..3 * pointer_size];
+    process.read_bytes(address, buffer)?;
+
+    let order = process.endianness();
+    let words: Vec<usize> = buffer
+        .chunks_exact(pointer_size)
+        .map(|word| pointer_from_bytes(word, order))
+        .collect();
+
+    Ok((words[0], words[1], words[2]))
+}
+
+/// Checks that the value is non-null and aligned to the target pointer size.
+pub(crate) fn is_aligned_pointer<P: Process + ?Sized>(process: &P, value: usize) -> bool {
+    value != 0
+        && process
+            .pointer_size()
+            .is_ok_and(|pointer_size| value.is_multiple_of(pointer_size))
+}
+
+/// Adds `offset` to the address, failing instead of wrapping around the address space.
+pub(crate) fn offset_address(address: usize, offset: usize) -> Result<usize, MemoryReadError> {
+    address
+        .checked_add(offset)
+        .ok_or(MemoryReadError::AddressOverflow { address, offset })
+}
+
+/// Reads pointer at the address and adds `offset`, rejecting null pointers.
+fn deref_pointer<P: Process + ?Sized>(
+    process: &P,
+    pointer_address: usize,
+    offset: usize,
+) -> Result<usize, MemoryReadError> {
+    match process.read_ptr(pointer_address)? {
+        0 => Err(MemoryReadError::NullPointer {
+            address: pointer_address,
+        }),
+        pointer => offset_address(pointer, offset),
+    }
+}
+
+macro_rules! define_deref_read (
+    ($type: ident, $name: ident, $read: ident) => (
+        fn $name(&self, pointer_address: usize, offset: usize) -> Result<$type, MemoryReadError> {
+            self.$read(deref_pointer(self, pointer_address, offset)?)
+        }
+    );
+);
+
 macro_rules! define_number_read (
-    ($type: ident, $name: ident, $bytes: expr) => (
+    ($type: ident, $name: ident, $name_with_order: ident, $bytes: expr) => (
         fn $name(&self, address: usize) -> Result<$type, MemoryReadError> {
+            self.$name_with_order(address, self.endianness())
+        }
+
+        fn $name_with_order(
+            &self,
+            address: usize,
+            order: Endianness,
+        ) -> Result<$type, MemoryReadError> {
             let mut buffer = [0u8; $bytes];
             self.read_bytes(address, &mut buffer)?;
 
-            Ok($type::from_le_bytes(buffer))
+            Ok(match order {
+                Endianness::Little => $type::from_le_bytes(buffer),
+                Endianness::Big => $type::from_be_bytes(buffer),
+            })
+        }
+   );
+);
+
+macro_rules! define_number_write (
+    ($type: ident, $name: ident, $name_with_order: ident) => (
+        fn $name(&self, address: usize, value: $type) -> Result<(), MemoryWriteError> {
+            self.$name_with_order(address, value, self.endianness())
+        }
+
+        fn $name_with_order(
+            &self,
+            address: usize,
+            value: $type,
+            order: Endianness,
+        ) -> Result<(), MemoryWriteError> {
+            let bytes = match order {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes(),
+            };
+
+            self.write_bytes(address, &bytes)
         }
    );
 );
 
 pub trait Process {
     /// Finds process module base address.
+    ///
+    /// Every call walks the module list again, so callers resolving bases repeatedly should
+    /// keep the result, or the list `modules` returns, themselves.
     fn base_address(&self, module_name: &str) -> Option<usize>;
 
+    /// Base address of the process executable image.
+    ///
+    /// The default returns the module with the lowest base, which usually, but not always, is
+    /// the executable; processes that can tell the executable apart override it.
+    fn main_module_base(&self) -> Option<usize> {
+        self.modules().ok()?.first().map(|module| module.base)
+    }
+
+    /// Finds loaded module by name, ignoring ASCII case.
+    fn find_module(&self, module_name: &str) -> Result<Module, MemoryReadError> {
+        self.modules()?
+            .into_iter()
+            .find(|module| module.name.eq_ignore_ascii_case(module_name))
+            .ok_or_else(|| MemoryReadError::ModuleNotFound {
+                name: module_name.to_owned(),
+            })
+    }
+
+    /// Base address of the module loaded from exactly this path.
+    ///
+    /// Windows paths are compared ignoring ASCII case.
+    fn base_address_by_path(&self, full_path: &str) -> Option<usize> {
+        let matches = |path: &Path| {
+            if cfg!(windows) {
+                path.to_string_lossy().eq_ignore_ascii_case(full_path)
+            } else {
+                path == Path::new(full_path)
+            }
+        };
+
+        self.modules()
+            .ok()?
+            .into_iter()
+            .find(|module| matches(&module.path))
+            .map(|module| module.base)
+    }
+
+    /// Finds loaded module whose image contains the address.
+    fn which_module(&self, address: usize) -> Result<Option<Module>, MemoryReadError> {
+        Ok(self
+            .modules()?
+            .into_iter()
+            .find(|module| module.contains(address)))
+    }
+
+    /// Name of the module containing the address and the offset from its base.
+    ///
+    /// The offset stays the same across runs wherever ASLR loads the module; resolve it back
+    /// with `from_module_relative`.
+    fn to_module_relative(&self, address: usize) -> Option<(String, usize)> {
+        let module = self.which_module(address).ok()??;
+        let offset = address - module.base;
+
+        Some((module.name, offset))
+    }
+
+    /// Absolute address of `offset` in the module, as returned by `to_module_relative`.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_module_relative(&self, module: &str, offset: usize) -> Option<usize> {
+        self.base_address(module)?.checked_add(offset)
+    }
+
+    /// Resolves absolute address of a symbol exported by the module.
+    ///
+    /// PE export tables are read from process memory; ELF symbols are read from the module file.
+    fn get_export(&self, module_name: &str, export: &str) -> Result<usize, MemoryReadError> {
+        self.module_exports(module_name)?.address_of(export)
+    }
+
+    /// Lists every export of the module with its absolute address, sorted by address.
+    ///
+    /// PE exports without a name are listed as `#<ordinal>`, so `get_export` resolves them too.
+    fn exports(&self, module_name: &str) -> Result<Vec<(String, usize)>, MemoryReadError> {
+        let module = self.find_module(module_name)?;
+        let mut exports = module::read_exports(self, &module)?;
+        exports.sort_by_key(|&(_, address)| address);
+
+        Ok(exports)
+    }
+
+    /// Reads `(dll, function, address)` of every import of the module, the address being the
+    /// current value of its import address table slot.
+    ///
+    /// Comparing the addresses with the exports of the DLLs reveals hooked imports. Only PE
+    /// modules are supported.
+    fn read_iat(&self, module_name: &str) -> Result<Vec<(String, String, usize)>, MemoryReadError> {
+        let module = self.find_module(module_name)?;
+
+        module::read_imports(self, &module)
+    }
+
+    /// Base address the module's file asks to be loaded at, `ImageBase` for PE and the lowest
+    /// `p_vaddr` for ELF files.
+    ///
+    /// Addresses recorded by static analysis tools are relative to this base.
+    fn module_preferred_base(&self, module_name: &str) -> Result<usize, MemoryReadError> {
+        let module = self.find_module(module_name)?;
+
+        module::preferred_base(self, &module)
+    }
+
+    /// Difference of the base the module is loaded at and its preferred base.
+    ///
+    /// Adding it to a statically recorded address gives the live address.
+    fn module_relocation_delta(&self, module_name: &str) -> Result<isize, MemoryReadError> {
+        let module = self.find_module(module_name)?;
+        let preferred_base = module::preferred_base(self, &module)?;
+
+        Ok(module.base.wrapping_sub(preferred_base) as isize)
+    }
+
+    /// Parses export table of the module once, for resolving many exports.
+    fn module_exports(&self, module_name: &str) -> Result<ModuleExports, MemoryReadError> {
+        let module = self.find_module(module_name)?;
+        let exports = module::read_exports(self, &module)?;
+
+        Ok(ModuleExports::new(module, exports))
+    }
+
+    /// Reads `u64` stored at a symbol exported by the module.
+    fn read_export_u64(&self, module_name: &str, export: &str) -> Result<u64, MemoryReadError> {
+        self.read_u64(self.get_export(module_name, export)?)
+    }
+
+    /// Polls `base_address` until the module is loaded or `timeout` elapses.
+    fn wait_for_module(
+        &self,
+        module_name: &str,
+        timeout: Duration,
+    ) -> Result<usize, MemoryReadError> {
+        let started = Instant::now();
+
+        loop {
+            if let Some(base) = self.base_address(module_name) {
+                return Ok(base);
+            }
+
+            let elapsed = started.elapsed();
+
+            if elapsed >= timeout {
+                return Err(MemoryReadError::Timeout { timeout });
+            }
+
+            sleep(MODULE_POLL_INTERVAL.min(timeout - elapsed));
+        }
+    }
+
+    /// Polls the `u32` at the address every `poll` until `predicate` accepts it or `timeout`
+    /// elapses, returning the accepted value.
+    fn wait_for_value_u32(
+        &self,
+        address: usize,
+        predicate: impl Fn(u32) -> bool,
+        timeout: Duration,
+        poll: Duration,
+    ) -> Result<u32, MemoryReadError> {
+        let started = Instant::now();
+
+        loop {
+            let value = self.read_u32(address)?;
+
+            if predicate(value) {
+                return Ok(value);
+            }
+
+            let elapsed = started.elapsed();
+
+            if elapsed >= timeout {
+                return Err(MemoryReadError::Timeout { timeout });
+            }
+
+            sleep(poll.min(timeout - elapsed));
+        }
+    }
+
     /// Read the specified length in bytes from the address memory.
     fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError>;
 
-    /// Read string until null char are read.
-    fn read_string(&self, address: usize) -> Result<String, MemoryReadError> {
-        let mut buffer = Vec::new();
-        let mut index = 0;
+    /// Reads as many bytes as possible into the start of `buffer` and returns their count.
+    ///
+    /// Unlike `read_bytes`, a short read is not an error.
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        match self.read_bytes(address, buffer) {
+            Ok(()) => Ok(buffer.len()),
+            Err(MemoryReadError::LessBytesRead { actual, .. }) => Ok(actual),
+            Err(error) => Err(error),
+        }
+    }
 
-        loop {
-            let ch = self.read_u8(address + index as usize)?;
+    /// Calls `read_bytes` up to `attempts` times, sleeping `backoff` between attempts.
+    ///
+    /// Only `InaccessibleMemoryAddress` and `LessBytesRead` are retried; other errors and the
+    /// error of the last attempt are returned as is.
+    fn read_bytes_retry(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+        attempts: usize,
+        backoff: Duration,
+    ) -> Result<(), MemoryReadError> {
+        let mut attempt = 1;
 
-            if ch == 0 {
-                break;
+        loop {
+            match self.read_bytes(address, buffer) {
+                Err(
+                    MemoryReadError::InaccessibleMemoryAddress { .. }
+                    | MemoryReadError::LessBytesRead { .. },
+                ) if attempt < attempts => {
+                    trace_event!(debug, address, attempt, "retrying read_bytes");
+                    attempt += 1;
+                    sleep(backoff);
+                }
+                result => return result,
             }
+        }
+    }
+
+    /// Lists committed memory regions sorted by base address.
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError>;
+
+    /// Lists loaded modules sorted by base address.
+    ///
+    /// The list is collected in one pass and not cached, so it reflects modules loaded or
+    /// unloaded since the previous call.
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError>;
+
+    /// Byte order the number readers decode with.
+    fn endianness(&self) -> Endianness {
+        Endianness::Little
+    }
+
+    /// Largest amount of bytes a single read is allowed to request.
+    fn max_read_size(&self) -> usize {
+        DEFAULT_MAX_READ_SIZE
+    }
+
+    /// Size of the memory pages chunked readers avoid reading across.
+    fn page_size(&self) -> usize {
+        PAGE_SIZE
+    }
 
-            buffer.insert(index, ch);
-            index += 1;
+    /// Fills uninitialized buffer from the address and returns it as initialized bytes.
+    ///
+    /// Platform processes read straight into the buffer, skipping the zeroing `read_bytes`
+    /// callers pay for.
+    fn read_bytes_uninit<'a>(
+        &self,
+        address: usize,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], MemoryReadError> {
+        for byte in buffer.iter_mut() {
+            *byte = MaybeUninit::new(0);
         }
 
-        Ok(String::from_utf8(buffer).unwrap_or(String::from("")))
+        let buffer = unsafe { assume_init_mut(buffer) };
+        self.read_bytes(address, buffer)?;
+
+        Ok(buffer)
     }
 
-    fn read_u8(&self, address: usize) -> Result<u8, MemoryReadError> {
-        let mut buffer = [0u8; 1];
-        self.read_bytes(address, &mut buffer)?;
+    /// Reads `len` bytes from the address into a new vec.
+    fn read_bytes_vec(&self, address: usize, len: usize) -> Result<Vec<u8>, MemoryReadError> {
+        check_read_size(len, self.max_read_size())?;
 
-        Ok(buffer[0])
+        let mut buffer = Vec::with_capacity(len);
+        self.read_bytes_uninit(address, &mut buffer.spare_capacity_mut()[..len])?;
+
+        unsafe { buffer.set_len(len) };
+
+        Ok(buffer)
     }
 
-    fn read_bool(&self, address: usize) -> Result<bool, MemoryReadError> {
-        Ok(self.read_u8(address)? == 1)
+    /// Same as `read_bytes_vec`, but returns a boxed slice, whose ownership can be handed over
+    /// as a pointer and length. Fails in the same cases as `read_bytes`.
+    ///
+    /// ```no_run
+    /// use process_memory_reader::{open_process, Process};
+    ///
+    /// /// Released with `Box::from_raw(slice::from_raw_parts_mut(ptr, len))`.
+    /// #[no_mangle]
+    /// pub extern "C" fn read_memory(pid: u32, address: usize, len: usize, out: *mut usize)
+    ///     -> *mut u8
+    /// {
+    ///     let bytes = open_process(pid)
+    ///         .and_then(|process| process.read_bytes_boxed(address, len).ok());
+    ///
+    ///     match bytes {
+    ///         Some(bytes) => {
+    ///             unsafe { *out = bytes.len() };
+    ///             Box::into_raw(bytes) as *mut u8
+    ///         }
+    ///         None => std::ptr::null_mut(),
+    ///     }
+    /// }
+    /// ```
+    fn read_bytes_boxed(&self, address: usize, len: usize) -> Result<Box<[u8]>, MemoryReadError> {
+        Ok(self.read_bytes_vec(address, len)?.into_boxed_slice())
+    }
+
+    /// Reads every `(address, len)` request into its own vec, in order.
+    ///
+    /// Platform processes batch the reads into as few calls as they can. The first failing
+    /// request fails the whole batch with `RequestFailed`, carrying its index.
+    fn read_many(&self, requests: &[(usize, usize)]) -> Result<Vec<Vec<u8>>, MemoryReadError> {
+        requests
+            .iter()
+            .enumerate()
+            .map(|(index, &(address, len))| {
+                self.read_bytes_vec(address, len).map_err(|read_error| {
+                    MemoryReadError::RequestFailed {
+                        index,
+                        read_error: Box::new(read_error),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a `u32` length at the address, then that many bytes following it.
+    ///
+    /// Lengths above `max_read_size` fail with `ReadTooLarge` before anything is allocated.
+    fn read_length_prefixed(&self, address: usize) -> Result<Vec<u8>, MemoryReadError> {
+        let len = self.read_u32(address)? as usize;
+
+        self.read_bytes_vec(offset_address(address, 4)?, len)
+    }
+
+    /// Reads the bytes from `start` up to `end`, exclusive.
+    ///
+    /// The range is read in chunks of at most `max_read_size` bytes, so it may be larger than
+    /// that. The buffer grows as chunks are read, so a bogus range fails at its first
+    /// unreadable chunk instead of allocating its whole length.
+    fn read_range(&self, start: usize, end: usize) -> Result<Vec<u8>, MemoryReadError> {
+        if end < start {
+            return Err(MemoryReadError::InvalidRange { start, end });
+        }
+
+        let chunk_size = SCAN_CHUNK_SIZE.min(self.max_read_size()).max(1);
+        let mut bytes = Vec::new();
+
+        for chunk_start in (start..end).step_by(chunk_size) {
+            let chunk_len = chunk_size.min(end - chunk_start);
+            bytes.reserve(chunk_len);
+            self.read_bytes_uninit(chunk_start, &mut bytes.spare_capacity_mut()[..chunk_len])?;
+
+            unsafe { bytes.set_len(bytes.len() + chunk_len) };
+        }
+
+        Ok(bytes)
+    }
+
+    /// Streams `len` bytes from the address to `writer` and returns the count of bytes written.
+    ///
+    /// The bytes are read and written in chunks, so regions of any size are dumped without
+    /// buffering them whole. Unreadable pages, such as unmapped gaps, are written as zeros, so
+    /// every byte of the output is at the offset of its address and the count is always `len`.
+    ///
+    /// ```no_run
+    /// use process_memory_reader::{open_process, Process};
+    /// use std::fs::File;
+    ///
+    /// let process = open_process(22212).unwrap();
+    /// let mut file = File::create("memory.bin").unwrap();
+    ///
+    /// for region in process.memory_regions().unwrap() {
+    ///     if region.readable {
+    ///         process.dump_region(region.base, region.size, &mut file).unwrap();
+    ///     }
+    /// }
+    /// ```
+    fn dump_region(
+        &self,
+        address: usize,
+        len: usize,
+        writer: &mut impl Write,
+    ) -> Result<usize, MemoryReadError> {
+        let end = offset_address(address, len)?;
+        let chunk_size = SCAN_CHUNK_SIZE.min(self.max_read_size()).max(1);
+        let page_size = self.page_size().max(1);
+        let mut buffer = vec![0u8; chunk_size.min(len)];
+        let mut current = address;
+
+        while current < end {
+            let chunk = &mut buffer[..chunk_size.min(end - current)];
+
+            let read = match self.read_bytes_partial(current, chunk) {
+                Ok(read) => read,
+                Err(MemoryReadError::InaccessibleMemoryAddress { .. }) => 0,
+                Err(error) => return Err(error),
+            };
+
+            // Short reads stop at an unreadable page, which is skipped to retry after it.
+            let unreadable = current + read;
+            let written = if read == chunk.len() {
+                read
+            } else {
+                // Without a next page the unreadable one holds the rest of the address space.
+                let written = (unreadable - unreadable % page_size)
+                    .checked_add(page_size)
+                    .map_or(chunk.len(), |next_page| {
+                        (next_page - current).min(chunk.len())
+                    });
+                chunk[read..written].fill(0);
+
+                written
+            };
+
+            writer.write_all(&chunk[..written])?;
+            current += written;
+        }
+
+        Ok(len)
+    }
+
+    /// Same as `dump_region`, but writes the bytes to a file created at `path`, replacing any
+    /// file already there.
+    fn dump_to_file(
+        &self,
+        address: usize,
+        len: usize,
+        path: impl AsRef<Path>,
+    ) -> Result<usize, MemoryReadError> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let written = self.dump_region(address, len, &mut file)?;
+        file.flush()?;
+
+        Ok(written)
+    }
+
+    /// Reads up to `max_len` bytes of code starting at the address, such as a function body to
+    /// disassemble.
+    ///
+    /// The read stops at the end of the executable region containing the address, so it never
+    /// runs into unmapped memory. Addresses outside executable regions fail with
+    /// `InaccessibleMemoryAddress`.
+    fn read_function(&self, address: usize, max_len: usize) -> Result<Vec<u8>, MemoryReadError> {
+        let regions = self.memory_regions()?;
+        let region = region::find_region(&regions, address)
+            .filter(|region| region.readable && region.executable)
+            .ok_or(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: None,
+            })?;
+
+        self.read_bytes_vec(address, max_len.min(region.end() - address))
+    }
+
+    /// Reads `len` bytes and hands them to `parse`, returning its result.
+    ///
+    /// Spans of up to `STACK_BUFFER_SIZE` bytes are read into a stack buffer.
+    fn read_and_parse<T, F: FnOnce(&[u8]) -> T>(
+        &self,
+        address: usize,
+        len: usize,
+        parse: F,
+    ) -> Result<T, MemoryReadError> {
+        if len > STACK_BUFFER_SIZE {
+            return Ok(parse(&self.read_bytes_vec(address, len)?));
+        }
+
+        let mut buffer = [MaybeUninit::<u8>::uninit(); STACK_BUFFER_SIZE];
+        let bytes = self.read_bytes_uninit(address, &mut buffer[..len])?;
+
+        Ok(parse(bytes))
+    }
+
+    /// Reads whole `T`, such as a `#[repr(C)]` struct, with a single read.
+    ///
+    /// ```no_run
+    /// use process_memory_reader::{open_process, Pod, Process};
+    ///
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy)]
+    /// struct Entity {
+    ///     hp: i32,
+    ///     position: [f32; 3],
+    /// }
+    ///
+    /// unsafe impl Pod for Entity {}
+    ///
+    /// let process = open_process(22212).unwrap();
+    /// let entity = process.read::<Entity>(0x7ff6_1234_5678).unwrap();
+    /// ```
+    ///
+    /// Fields are kept as laid out in memory unless `T` overrides `Pod::to_host_order`.
+    fn read<T: Pod>(&self, address: usize) -> Result<T, MemoryReadError> {
+        read_pod(self, address)
+    }
+
+    /// Same as `read`, but overwrites `out` instead of returning the value.
+    fn read_into<T: Pod>(&self, address: usize, out: &mut T) -> Result<(), MemoryReadError> {
+        self.read_into_slice(address, std::slice::from_mut(out))
+    }
+
+    /// Fills `buffer` with consecutive values read from the address.
+    ///
+    /// Exactly `buffer.len() * size_of::<T>()` bytes are read straight into the slice, then
+    /// every value is converted from the process byte order with `Pod::to_host_order`.
+    fn read_into_slice<T: Pod>(
+        &self,
+        address: usize,
+        buffer: &mut [T],
+    ) -> Result<(), MemoryReadError> {
+        self.read_bytes(address, pod::bytes_of_slice_mut(buffer))?;
+
+        let order = self.endianness();
+
+        for value in buffer.iter_mut() {
+            *value = value.to_host_order(order);
+        }
+
+        Ok(())
+    }
+
+    /// Reads `count` consecutive values with a single read.
+    fn read_slice<T: Pod>(&self, address: usize, count: usize) -> Result<Vec<T>, MemoryReadError> {
+        check_read_size(count.saturating_mul(size_of::<T>()), self.max_read_size())?;
+
+        let mut values = vec![pod::zeroed::<T>(); count];
+
+        if count > 0 {
+            self.read_into_slice(address, &mut values)?;
+        }
+
+        Ok(values)
+    }
+
+    /// Same as `read_slice`, but for a count known at compile time.
+    fn read_array<T: Pod, const N: usize>(
+        &self,
+        address: usize,
+    ) -> Result<[T; N], MemoryReadError> {
+        let mut values = [pod::zeroed::<T>(); N];
+        self.read_into_slice(address, &mut values)?;
+
+        Ok(values)
+    }
+
+    /// Iterates over `count` consecutive records starting at the address.
+    fn iter_records<T: Pod>(&self, address: usize, count: usize) -> Records<'_, Self, T> {
+        Records::new(self, address, count)
+    }
+
+    /// Reads `count` values spaced `stride` bytes apart, such as array elements with padding.
+    ///
+    /// The whole span is read at once and every value is copied out of it locally.
+    fn read_strided<T: Pod>(
+        &self,
+        address: usize,
+        count: usize,
+        stride: usize,
+    ) -> Result<Vec<T>, MemoryReadError> {
+        let size = size_of::<T>();
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let span = (count - 1).saturating_mul(stride).saturating_add(size);
+        check_read_size(span, self.max_read_size())?;
+
+        let bytes = self.read_bytes_vec(address, span)?;
+        let order = self.endianness();
+
+        Ok((0..count)
+            .map(|index| {
+                let mut value = [pod::zeroed::<T>()];
+                let offset = index * stride;
+                pod::bytes_of_slice_mut(&mut value).copy_from_slice(&bytes[offset..offset + size]);
+
+                value[0].to_host_order(order)
+            })
+            .collect())
+    }
+
+    /// View of the process that passes the addresses given to its readers through `rebase`
+    /// first, leaving pointers read from memory untouched.
+    ///
+    /// For offsets recorded against preferred base `P` of a module loaded at `B`:
+    /// `process.rebased(|address| address - P + B)`. Pointer chains then start at the rebased
+    /// address and follow the runtime pointers they read as they are.
+    fn rebased<F: Fn(usize) -> usize>(&self, rebase: F) -> Rebased<'_, Self, F> {
+        Rebased::new(self, rebase)
+    }
+
+    /// Reads `T` at the address only if the bytes at `address + magic_offset` are `expected_magic`.
+    fn read_checked<T: Pod>(
+        &self,
+        address: usize,
+        magic_offset: usize,
+        expected_magic: &[u8],
+    ) -> Result<T, MemoryReadError> {
+        let magic_address = offset_address(address, magic_offset)?;
+        let magic = self.read_bytes_vec(magic_address, expected_magic.len())?;
+
+        if magic != expected_magic {
+            return Err(MemoryReadError::MagicMismatch {
+                address: magic_address,
+                expected: expected_magic.to_vec(),
+                actual: magic,
+            });
+        }
+
+        read_pod(self, address)
+    }
+
+    /// Scans readable memory for `value`, calling `on_match` with the address of every hit
+    /// as soon as it is found.
+    ///
+    /// Only addresses aligned to `align_of::<T>()` are checked. Scanning stops once `on_match`
+    /// returns `ControlFlow::Break`.
+    fn scan_value_cb<T, F>(&self, value: T, on_match: F) -> Result<(), MemoryReadError>
+    where
+        T: Pod + PartialEq,
+        F: FnMut(usize) -> ControlFlow<()>,
+    {
+        scan::scan_value(self, 0..usize::MAX, value, on_match)
+    }
+
+    /// Returns the addresses of every `value` in the readable memory of the `len` bytes from
+    /// `start`.
+    ///
+    /// Only addresses aligned to `align_of::<T>()` are checked, and unreadable regions such as
+    /// guard pages are skipped.
+    fn find_value<T: Pod + PartialEq>(
+        &self,
+        start: usize,
+        len: usize,
+        value: T,
+    ) -> Result<Vec<usize>, MemoryReadError> {
+        let end = offset_address(start, len)?;
+        let mut addresses = Vec::new();
+
+        scan::scan_value(self, start..end, value, |address| {
+            addresses.push(address);
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(addresses)
+    }
+
+    /// Keeps the `candidates`, such as addresses `find_value` returned, that still hold `value`.
+    ///
+    /// Candidates that can no longer be read are dropped.
+    fn filter_value<T: Pod + PartialEq>(&self, candidates: &[usize], value: T) -> Vec<usize> {
+        candidates
+            .iter()
+            .copied()
+            .filter(|&address| read_pod(self, address).is_ok_and(|current: T| current == value))
+            .collect()
+    }
+
+    /// Reads `len` bytes from `start` and returns the address of the first occurrence of the
+    /// UTF-8 bytes of `needle` in them.
+    fn find_string(
+        &self,
+        start: usize,
+        len: usize,
+        needle: &str,
+    ) -> Result<Option<usize>, MemoryReadError> {
+        if needle.is_empty() {
+            return Ok(Some(start));
+        }
+
+        let bytes = self.read_bytes_vec(start, len)?;
+
+        Ok(bytes
+            .windows(needle.len())
+            .position(|window| window == needle.as_bytes())
+            .map(|offset| start + offset))
+    }
+
+    /// Reads the `len` bytes from `start` in chunks and returns the address of the first match
+    /// of the pattern, such as one made by `parse_pattern`, with `None` entries matching any
+    /// byte.
+    fn find_pattern(
+        &self,
+        start: usize,
+        len: usize,
+        pattern: &[Option<u8>],
+    ) -> Result<Option<usize>, MemoryReadError> {
+        scan::find_pattern(self, start, len, pattern)
+    }
+
+    /// Copies `len` bytes starting at the address into a snapshot that can later be diffed.
+    fn snapshot(&self, address: usize, len: usize) -> Result<MemorySnapshot, MemoryReadError> {
+        let buffer = self.read_bytes_vec(address, len)?;
+
+        Ok(MemorySnapshot::new(address, buffer))
+    }
+
+    /// Copies `len` bytes starting at the address into a view that is indexed without reading
+    /// the process again.
+    fn map_region(&self, address: usize, len: usize) -> Result<RegionView, MemoryReadError> {
+        let buffer = self.read_bytes_vec(address, len)?;
+
+        Ok(RegionView::new(address, buffer, self.endianness()))
+    }
+
+    /// Starts watching `size` bytes at each of the addresses for changes between polls.
+    fn watch(&self, addresses: &[usize], size: usize) -> Watcher<'_, Self> {
+        Watcher::new(self, addresses, size)
+    }
+
+    /// Reads all fields with a single read spanning them, decoding each one locally.
+    ///
+    /// Values are returned in the order of `fields`.
+    fn read_struct_fields(
+        &self,
+        base: usize,
+        fields: &[FieldSpec],
+    ) -> Result<Vec<FieldValue>, MemoryReadError> {
+        if fields.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pointer_size = self.pointer_size()?;
+        let order = self.endianness();
+        let start = fields.iter().map(|field| field.offset).min().unwrap_or(0);
+        let mut end = 0;
+
+        for field in fields {
+            end = end.max(offset_address(field.offset, field.kind.size(pointer_size))?);
+        }
+
+        let bytes = self.read_bytes_vec(offset_address(base, start)?, end - start)?;
+
+        Ok(fields
+            .iter()
+            .map(|field| {
+                field
+                    .kind
+                    .decode(&bytes[field.offset - start..], pointer_size, order)
+            })
+            .collect())
+    }
+
+    /// Reads every field of the layout with a single read spanning them, keyed by field name.
+    fn read_described(
+        &self,
+        base: usize,
+        layout: &Layout,
+    ) -> Result<HashMap<String, FieldValue>, MemoryReadError> {
+        let values = self.read_struct_fields(base, &layout.specs())?;
+
+        Ok(layout
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .zip(values)
+            .collect())
+    }
+
+    /// Reads the `u32` tag at `tag_offset` and decodes the payload at `payload_offset` as the kind
+    /// `variants` lists for it, returning `(tag, payload)`.
+    ///
+    /// Tags missing from `variants` fail with `InvalidEnum`.
+    fn read_tagged_union(
+        &self,
+        address: usize,
+        tag_offset: usize,
+        payload_offset: usize,
+        variants: &[(u32, FieldKind)],
+    ) -> Result<(u32, FieldValue), MemoryReadError> {
+        let tag = self.read_u32(offset_address(address, tag_offset)?)?;
+        let &(_, kind) = variants
+            .iter()
+            .find(|&&(variant, _)| variant == tag)
+            .ok_or(MemoryReadError::InvalidEnum { value: tag })?;
+
+        let mut values =
+            self.read_struct_fields(address, &[FieldSpec::new(payload_offset, kind)])?;
+
+        Ok((tag, values.remove(0)))
+    }
+
+    /// Read string until null char are read.
+    ///
+    /// Reaching unreadable memory before the null char fails with the read error; use
+    /// `read_string_until_unreadable` to keep the readable prefix.
+    ///
+    /// Strings longer than `MAX_STRING_LEN` bytes are cut off there. Invalid UTF-8 sequences are
+    /// replaced with `U+FFFD`; use `read_string_strict` to fail on them instead.
+    fn read_string(&self, address: usize) -> Result<String, MemoryReadError> {
+        self.read_string_limited(address, MAX_STRING_LEN)
+    }
+
+    /// Read string until null char or `max_len` bytes are read, returning the bytes read so far
+    /// when no null char was found.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    fn read_string_limited(
+        &self,
+        address: usize,
+        max_len: usize,
+    ) -> Result<String, MemoryReadError> {
+        let buffer = read_until_null(self, address, STRING_CHUNK_SIZE, max_len, false)?;
+
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    /// Same as `read_string`, but invalid UTF-8 fails with `InvalidUtf8` carrying the bytes read.
+    fn read_string_strict(&self, address: usize) -> Result<String, MemoryReadError> {
+        let buffer = read_until_null(self, address, STRING_CHUNK_SIZE, MAX_STRING_LEN, false)?;
+
+        String::from_utf8(buffer).map_err(|error| MemoryReadError::InvalidUtf8 {
+            bytes: error.into_bytes(),
+        })
+    }
+
+    /// Reads string until null char or the first unreadable byte, such as the end of a mapped
+    /// region.
+    ///
+    /// Fails only when not even the first byte can be read. Invalid UTF-8 sequences are replaced
+    /// with `U+FFFD`.
+    fn read_string_until_unreadable(&self, address: usize) -> Result<String, MemoryReadError> {
+        let bytes = read_until_null(self, address, STRING_CHUNK_SIZE, self.max_read_size(), true)?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads exactly `len` bytes as UTF-8, keeping any null chars they contain.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    fn read_utf8(&self, address: usize, len: usize) -> Result<String, MemoryReadError> {
+        let bytes = self.read_bytes_vec(address, len)?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads string of at most `max` bytes until null char, `chunk` bytes per read.
+    ///
+    /// Reads never cross a page boundary, and a failed read is retried with a halved chunk. A
+    /// `chunk` of 0 reads byte by byte. Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    fn read_string_chunked(
+        &self,
+        address: usize,
+        chunk: usize,
+        max: usize,
+    ) -> Result<String, MemoryReadError> {
+        let bytes = read_until_null(self, address, chunk, max, false)?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads string until null char and decodes it with `encoding`.
+    ///
+    /// Strings are cut off at `MAX_STRING_LEN` bytes like `read_string` does it, and UTF-16 ones
+    /// end at a null code unit instead of a null byte.
+    fn read_cstring(
+        &self,
+        address: usize,
+        encoding: StringEncoding,
+    ) -> Result<String, MemoryReadError> {
+        let order = match encoding {
+            StringEncoding::Utf8 => return self.read_string(address),
+            StringEncoding::Latin1 => {
+                let bytes =
+                    read_until_null(self, address, STRING_CHUNK_SIZE, MAX_STRING_LEN, false)?;
+
+                return Ok(bytes.into_iter().map(char::from).collect());
+            }
+            StringEncoding::Utf16Le => Endianness::Little,
+            StringEncoding::Utf16Be => Endianness::Big,
+        };
+
+        let bytes =
+            read_until_null_unit(self, address, 2, STRING_CHUNK_SIZE, MAX_STRING_LEN, false)?;
+
+        Ok(decode_utf16(&bytes, order))
+    }
+
+    /// Reads string until null char and decodes it with `encoding`, such as Shift-JIS.
+    ///
+    /// The string ends at the first null byte, so UTF-16 strings are cut short. Malformed
+    /// sequences are replaced with `U+FFFD`.
+    #[cfg(feature = "encoding_rs")]
+    fn read_string_encoded(
+        &self,
+        address: usize,
+        encoding: &'static encoding_rs::Encoding,
+    ) -> Result<String, MemoryReadError> {
+        let bytes = read_until_null(
+            self,
+            address,
+            STRING_CHUNK_SIZE,
+            self.max_read_size(),
+            false,
+        )?;
+        let (string, _) = encoding.decode_without_bom_handling(&bytes);
+
+        Ok(string.into_owned())
+    }
+
+    /// Reads `count` null-terminated strings stored back to back starting at the address.
+    ///
+    /// Strings are decoded the same way `read_string` does it. As the next string starts after
+    /// the null char, one longer than `MAX_STRING_LEN` bytes fails with `InvalidLength` instead
+    /// of being cut off.
+    fn read_packed_strings(
+        &self,
+        address: usize,
+        count: usize,
+    ) -> Result<Vec<String>, MemoryReadError> {
+        let mut strings = Vec::with_capacity(count);
+        let mut cursor = address;
+
+        for _ in 0..count {
+            let buffer =
+                read_until_null(self, cursor, STRING_CHUNK_SIZE, MAX_STRING_LEN + 1, false)?;
+
+            if buffer.len() > MAX_STRING_LEN {
+                return Err(MemoryReadError::InvalidLength {
+                    length: buffer.len(),
+                    max: MAX_STRING_LEN,
+                });
+            }
+
+            cursor = offset_address(cursor, buffer.len() + 1)?;
+            strings.push(String::from_utf8_lossy(&buffer).into_owned());
+        }
+
+        Ok(strings)
+    }
+
+    /// Reads `count` pointers from the array and the C string each one points to.
+    ///
+    /// Null pointers yield empty strings.
+    fn read_string_table(
+        &self,
+        array_address: usize,
+        count: usize,
+    ) -> Result<Vec<String>, MemoryReadError> {
+        let pointer_size = self.pointer_size()?;
+
+        (0..count)
+            .map(|index| {
+                match self.read_ptr(offset_address(array_address, index * pointer_size)?)? {
+                    0 => Ok(String::new()),
+                    pointer => self.read_string(pointer),
+                }
+            })
+            .collect()
+    }
+
+    /// Reads .NET `System.String` object laid out as the CLR does it.
+    ///
+    /// The object header is a single method table pointer, so the length follows at
+    /// `pointer_size` bytes.
+    fn read_dotnet_string(&self, object_address: usize) -> Result<String, MemoryReadError> {
+        let header_size = self.pointer_size()?;
+
+        self.read_dotnet_string_with_header(object_address, header_size)
+    }
+
+    /// Reads .NET string whose `i32` length follows a `header_size` bytes object header.
+    ///
+    /// Mono objects carry a vtable and a sync pointer, giving a header of two pointers.
+    fn read_dotnet_string_with_header(
+        &self,
+        object_address: usize,
+        header_size: usize,
+    ) -> Result<String, MemoryReadError> {
+        let length_address = offset_address(object_address, header_size)?;
+        let length = self.read_i32(length_address)?;
+
+        let length = usize::try_from(length).map_err(|_| MemoryReadError::InvalidFormat {
+            reason: "string length is negative",
+        })?;
+        let size = length
+            .checked_mul(2)
+            .ok_or(MemoryReadError::InvalidLength {
+                length,
+                max: usize::MAX / 2,
+            })?;
+
+        let bytes = self.read_bytes_vec(offset_address(length_address, 4)?, size)?;
+
+        Ok(decode_utf16(&bytes, self.endianness()))
+    }
+
+    /// Reads UTF-16 string, such as a Windows wide string, until null code unit.
+    ///
+    /// Units are read in chunks and decoded once the terminator is found, so surrogate pairs
+    /// stay intact; unpaired surrogates are replaced with `U+FFFD`. Strings longer than
+    /// `MAX_STRING_LEN` bytes are cut off there.
+    fn read_string_utf16(&self, address: usize) -> Result<String, MemoryReadError> {
+        let bytes =
+            read_until_null_unit(self, address, 2, STRING_CHUNK_SIZE, MAX_STRING_LEN, false)?;
+
+        Ok(decode_utf16(&bytes, self.endianness()))
+    }
+
+    /// Reads UTF-32 string until null code point.
+    ///
+    /// Code points are read in chunks like `read_string_utf16` does it, and strings longer than
+    /// `MAX_STRING_LEN` bytes are cut off there. Invalid code points are replaced with `U+FFFD`.
+    fn read_utf32_string(&self, address: usize) -> Result<String, MemoryReadError> {
+        let bytes =
+            read_until_null_unit(self, address, 4, STRING_CHUNK_SIZE, MAX_STRING_LEN, false)?;
+        let order = self.endianness();
+
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|unit| {
+                let unit = [unit[0], unit[1], unit[2], unit[3]];
+                let code_point = match order {
+                    Endianness::Little => u32::from_le_bytes(unit),
+                    Endianness::Big => u32::from_be_bytes(unit),
+                };
+
+                char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER)
+            })
+            .collect())
+    }
+
+    fn read_u8(&self, address: usize) -> Result<u8, MemoryReadError> {
+        let mut buffer = [0u8; 1];
+        self.read_bytes(address, &mut buffer)?;
+
+        Ok(buffer[0])
+    }
+
+    fn read_i8(&self, address: usize) -> Result<i8, MemoryReadError> {
+        Ok(self.read_u8(address)? as i8)
+    }
+
+    fn read_bool(&self, address: usize) -> Result<bool, MemoryReadError> {
+        Ok(self.read_u8(address)? == 1)
+    }
+
+    /// Reads 16 bytes of a `GUID` as stored in memory; see [`format_guid`] for the layout.
+    fn read_guid(&self, address: usize) -> Result<[u8; 16], MemoryReadError> {
+        let mut guid = [0u8; 16];
+        self.read_bytes(address, &mut guid)?;
+
+        Ok(guid)
+    }
+
+    /// Reads byte of packed flags.
+    fn read_flags8(&self, address: usize) -> Result<u8, MemoryReadError> {
+        self.read_u8(address)
+    }
+
+    /// Reads `ceil(bit_count / 8)` bytes and expands their first `bit_count` bits.
+    fn read_bitset(
+        &self,
+        address: usize,
+        bit_count: usize,
+        order: BitOrder,
+    ) -> Result<Vec<bool>, MemoryReadError> {
+        let bytes = self.read_bytes_vec(address, bit_count.div_ceil(8))?;
+
+        Ok((0..bit_count)
+            .map(|bit| {
+                let shift = match order {
+                    BitOrder::LsbFirst => bit % 8,
+                    BitOrder::MsbFirst => 7 - bit % 8,
+                };
+
+                bytes[bit / 8] & (1 << shift) != 0
+            })
+            .collect())
+    }
+
+    /// Reads `bit_width` bits (up to 64) starting `bit_offset` bits after `base`.
+    ///
+    /// Bits are numbered from the least significant bit of each byte, as in little-endian C
+    /// bitfields, and only the bytes covering the range are read.
+    fn read_bits(
+        &self,
+        base: usize,
+        bit_offset: usize,
+        bit_width: usize,
+    ) -> Result<u64, MemoryReadError> {
+        if bit_width == 0 || bit_width > 64 {
+            return Err(MemoryReadError::InvalidLength {
+                length: bit_width,
+                max: 64,
+            });
+        }
+
+        let shift = bit_offset % 8;
+        let address = offset_address(base, bit_offset / 8)?;
+        let mut bytes = [0u8; 16];
+        self.read_bytes(address, &mut bytes[..(shift + bit_width).div_ceil(8)])?;
+
+        let value = u128::from_le_bytes(bytes) >> shift;
+
+        Ok(value as u64 & (u64::MAX >> (64 - bit_width)))
+    }
+
+    /// Reads `bitflags` value, keeping bits that do not correspond to a defined flag.
+    #[cfg(feature = "bitflags")]
+    fn read_bitflags<F: bitflags::Flags>(&self, address: usize) -> Result<F, MemoryReadError>
+    where
+        F::Bits: Pod,
+    {
+        Ok(F::from_bits_retain(read_pod(self, address)?))
+    }
+
+    /// Reads integer of `byte_len` (1 to 8) bytes, sign- or zero-extending it to `i64`.
+    fn read_int(
+        &self,
+        address: usize,
+        byte_len: usize,
+        signed: bool,
+    ) -> Result<i64, MemoryReadError> {
+        if byte_len == 0 || byte_len > 8 {
+            return Err(MemoryReadError::InvalidLength {
+                length: byte_len,
+                max: 8,
+            });
+        }
+
+        let mut bytes = [0u8; 8];
+        self.read_bytes(address, &mut bytes[..byte_len])?;
+
+        let unused_bits = 64 - 8 * byte_len;
+        let value = match self.endianness() {
+            Endianness::Little => u64::from_le_bytes(bytes),
+            Endianness::Big => u64::from_be_bytes(bytes) >> unused_bits,
+        };
+
+        if signed {
+            Ok(((value << unused_bits) as i64) >> unused_bits)
+        } else {
+            Ok(value as i64)
+        }
+    }
+
+    /// Reads fixed-point number stored as `byte_len` bytes integer with `frac_bits` fraction bits.
+    fn read_fixed(
+        &self,
+        address: usize,
+        frac_bits: u32,
+        byte_len: usize,
+        signed: bool,
+    ) -> Result<f64, MemoryReadError> {
+        let value = self.read_int(address, byte_len, signed)?;
+        let value = if signed {
+            value as f64
+        } else {
+            value as u64 as f64
+        };
+
+        Ok(value / 2f64.powi(frac_bits as i32))
+    }
+
+    /// Reads signed 16.16 fixed-point number.
+    fn read_fixed_16_16(&self, address: usize) -> Result<f64, MemoryReadError> {
+        Ok(self.read_i32(address)? as f64 / 65536.0)
+    }
+
+    /// Reads two consecutive `f32` values.
+    fn read_vec2f(&self, address: usize) -> Result<[f32; 2], MemoryReadError> {
+        read_f32_array(self, address)
+    }
+
+    /// Reads three consecutive `f32` values.
+    fn read_vec3f(&self, address: usize) -> Result<[f32; 3], MemoryReadError> {
+        read_f32_array(self, address)
+    }
+
+    /// Reads four consecutive `f32` values.
+    fn read_vec4f(&self, address: usize) -> Result<[f32; 4], MemoryReadError> {
+        read_f32_array(self, address)
+    }
+
+    /// Reads 8-bit RGBA color stored as `r, g, b, a` bytes.
+    fn read_color_rgba8(&self, address: usize) -> Result<[u8; 4], MemoryReadError> {
+        self.read_color(address, ColorOrder::Rgba)
+    }
+
+    /// Reads 8-bit color whose channels are stored in `order`, returning `[r, g, b, a]`.
+    ///
+    /// The order is of the bytes in memory, so a little-endian `0xAARRGGBB` integer is `Bgra`.
+    fn read_color(&self, address: usize, order: ColorOrder) -> Result<[u8; 4], MemoryReadError> {
+        let mut channels = [0u8; 4];
+        self.read_bytes(address, &mut channels)?;
+
+        Ok(order.to_rgba(channels))
+    }
+
+    /// Reads 4x4 `f32` matrix, keeping rows as they are laid out in memory.
+    fn read_matrix4x4(&self, address: usize) -> Result<[[f32; 4]; 4], MemoryReadError> {
+        let values: [f32; 16] = read_f32_array(self, address)?;
+        let mut matrix = [[0.0; 4]; 4];
+
+        for (row, values) in matrix.iter_mut().zip(values.chunks_exact(4)) {
+            row.copy_from_slice(values);
+        }
+
+        Ok(matrix)
+    }
+
+    #[cfg(feature = "glam")]
+    fn read_glam_vec2(&self, address: usize) -> Result<glam::Vec2, MemoryReadError> {
+        self.read_vec2f(address).map(glam::Vec2::from)
+    }
+
+    #[cfg(feature = "glam")]
+    fn read_glam_vec3(&self, address: usize) -> Result<glam::Vec3, MemoryReadError> {
+        self.read_vec3f(address).map(glam::Vec3::from)
+    }
+
+    #[cfg(feature = "glam")]
+    fn read_glam_vec4(&self, address: usize) -> Result<glam::Vec4, MemoryReadError> {
+        self.read_vec4f(address).map(glam::Vec4::from)
+    }
+
+    /// Reads `u32` discriminant and converts it into the enum.
+    fn read_enum<E: TryFrom<u32>>(&self, address: usize) -> Result<E, MemoryReadError> {
+        let value = self.read_u32(address)?;
+
+        E::try_from(value).map_err(|_| MemoryReadError::InvalidEnum { value })
+    }
+
+    /// Size in bytes (4 or 8) of a pointer in the target process.
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(size_of::<usize>())
+    }
+
+    /// Reads a pointer sized for the target process.
+    fn read_ptr(&self, address: usize) -> Result<usize, MemoryReadError> {
+        match self.pointer_size()? {
+            4 => Ok(self.read_u32(address)? as usize),
+            _ => Ok(self.read_u64(address)? as usize),
+        }
+    }
+
+    /// Reads address of virtual function `vtable_index` from the vtable of the object.
+    fn read_vfunc(
+        &self,
+        object_address: usize,
+        vtable_index: usize,
+    ) -> Result<usize, MemoryReadError> {
+        let vtable = self.read_ptr(object_address)?;
+        let pointer_size = self.pointer_size()?;
+        let offset =
+            vtable_index
+                .checked_mul(pointer_size)
+                .ok_or(MemoryReadError::InvalidLength {
+                    length: vtable_index,
+                    max: usize::MAX / pointer_size,
+                })?;
+
+        self.read_ptr(offset_address(vtable, offset)?)
+    }
+
+    /// Reads `u32`, returning `None` when the address is not mapped.
+    fn try_read_u32(&self, address: usize) -> Result<Option<u32>, MemoryReadError> {
+        unmapped_as_none(self.read_u32(address))
+    }
+
+    /// Reads `u64`, returning `None` when the address is not mapped.
+    fn try_read_u64(&self, address: usize) -> Result<Option<u64>, MemoryReadError> {
+        unmapped_as_none(self.read_u64(address))
+    }
+
+    /// Reads a pointer, returning `None` when the address is not mapped.
+    fn try_read_ptr(&self, address: usize) -> Result<Option<usize>, MemoryReadError> {
+        unmapped_as_none(self.read_ptr(address))
+    }
+
+    /// Reads a pointer and resolves which module, if any, it points into.
+    fn read_pointer(&self, address: usize) -> Result<ResolvedPointer, MemoryReadError> {
+        let value = self.read_ptr(address)?;
+        let module = self
+            .which_module(value)?
+            .map(|module| (module.name, value - module.base));
+
+        Ok(ResolvedPointer {
+            value,
+            plausible: self.is_plausible_pointer(value),
+            module,
+        })
+    }
+
+    /// Reads `i32` offset at the address and returns it added to `base`.
+    fn read_relative_ptr32(&self, address: usize, base: usize) -> Result<usize, MemoryReadError> {
+        let offset = self.read_i32(address)?;
+
+        Ok(base.wrapping_add_signed(offset as isize))
+    }
+
+    /// Same as `read_relative_ptr32`, but the offset is read as `u32`.
+    fn read_relative_ptr32_unsigned(
+        &self,
+        address: usize,
+        base: usize,
+    ) -> Result<usize, MemoryReadError> {
+        let offset = self.read_u32(address)?;
+
+        Ok(base.wrapping_add(offset as usize))
+    }
+
+    /// Resolves the RIP-relative operand of the instruction at the address, such as the one of
+    /// `mov rax, [rip + disp32]`.
+    ///
+    /// The signed `disp32` at `offset` into the instruction is added to the address of the next
+    /// instruction, `instruction_len` bytes on.
+    fn resolve_rip_relative(
+        &self,
+        instruction_address: usize,
+        offset: usize,
+        instruction_len: usize,
+    ) -> Result<usize, MemoryReadError> {
+        self.read_relative_ptr32(
+            offset_address(instruction_address, offset)?,
+            offset_address(instruction_address, instruction_len)?,
+        )
+    }
+
+    /// Decodes the x86 branch at the address and returns where it lands.
+    ///
+    /// Recognizes `jmp rel32` (`E9`), `call rel32` (`E8`) and `jmp [mem]` (`FF 25`), whose
+    /// pointer is read from a RIP-relative slot on 64-bit targets and an absolute one on 32-bit
+    /// targets. Other instructions return `None`.
+    fn read_branch_target(
+        &self,
+        instruction_address: usize,
+    ) -> Result<Option<usize>, MemoryReadError> {
+        let displacement = |offset| offset_address(instruction_address, offset);
+
+        match self.read_u8(instruction_address)? {
+            0xe8 | 0xe9 => Ok(Some(
+                self.read_relative_ptr32(displacement(1)?, displacement(5)?)?,
+            )),
+            0xff if self.read_u8(displacement(1)?)? == 0x25 => {
+                let slot = match self.pointer_size()? {
+                    8 => self.read_relative_ptr32(displacement(2)?, displacement(6)?)?,
+                    _ => self.read_u32(displacement(2)?)? as usize,
+                };
+
+                Ok(Some(self.read_ptr(slot)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Follows a pointer chain starting with the pointer stored at `base`, returning the address
+    /// the last offset points to.
+    ///
+    /// Every offset but the last is added and dereferenced; the last one is only added. Pointers
+    /// are `pointer_size` wide, and a null one along the chain fails with `NullPointer`.
+    fn read_pointer_chain(&self, base: usize, offsets: &[usize]) -> Result<usize, MemoryReadError> {
+        // Without offsets the pointer at `base` itself is the result.
+        if offsets.is_empty() {
+            return deref_pointer(self, base, 0);
+        }
+
+        let mut address = base;
+
+        for &offset in offsets {
+            address = deref_pointer(self, address, offset)?;
+        }
+
+        Ok(address)
+    }
+
+    /// Same as `read_pointer_chain`, but returns `None` when any pointer along the chain is
+    /// null.
+    fn try_read_pointer_chain(
+        &self,
+        base: usize,
+        offsets: &[usize],
+    ) -> Result<Option<usize>, MemoryReadError> {
+        let mut address = self.read_ptr(base)?;
+
+        if address == 0 {
+            return Ok(None);
+        }
+
+        let (last, offsets) = match offsets.split_last() {
+            Some(split) => split,
+            None => return Ok(Some(address)),
+        };
+
+        for offset in offsets {
+            address = self.read_ptr(offset_address(address, *offset)?)?;
+
+            if address == 0 {
+                return Ok(None);
+            }
+        }
+
+        offset_address(address, *last).map(Some)
+    }
+
+    /// Follows pointer chain like a Cheat Engine pointer entry and reads `T` at its end.
+    ///
+    /// For every offset the pointer at the current address is dereferenced and the offset added,
+    /// so `[a, b]` reads `T` at `[[base] + a] + b`. A null pointer along the chain fails with
+    /// `NullPointer`.
+    fn read_chain<T: Pod>(&self, base: usize, offsets: &[usize]) -> Result<T, MemoryReadError> {
+        let mut address = base;
+
+        for &offset in offsets {
+            address = deref_pointer(self, address, offset)?;
+        }
+
+        read_pod(self, address)
+    }
+
+    /// Checks that the value is a non-null, pointer-aligned address of readable memory.
+    ///
+    /// Platform processes answer from a region list cached according to their `CachePolicy`.
+    fn is_plausible_pointer(&self, value: usize) -> bool {
+        is_aligned_pointer(self, value)
+            && self.memory_regions().is_ok_and(|regions| {
+                region::find_region(&regions, value).is_some_and(|region| region.readable)
+            })
+    }
+
+    /// Collects node addresses of a linked list by following the pointer at `node + next_offset`.
+    ///
+    /// Stops at a null pointer, at the first revisited node or after `max_nodes` nodes.
+    fn read_linked_list(
+        &self,
+        head: usize,
+        next_offset: usize,
+        max_nodes: usize,
+    ) -> Result<Vec<usize>, MemoryReadError> {
+        let mut nodes = Vec::new();
+        let mut visited = HashSet::new();
+        let mut node = head;
+
+        while node != 0 && nodes.len() < max_nodes && visited.insert(node) {
+            nodes.push(node);
+
+            if nodes.len() < max_nodes {
+                node = self.read_ptr(offset_address(node, next_offset)?)?;
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Collects `(key, value)` entries of a chained hash table described by the layout.
+    fn read_hashmap<K: Pod, V: Pod>(
+        &self,
+        address: usize,
+        layout: HashMapLayout,
+    ) -> Result<Vec<(K, V)>, MemoryReadError> {
+        collections::read_hashmap(self, address, &layout)
+    }
+
+    /// Collects `(key, value)` entries of a binary search tree in order, starting at `root` node.
+    fn read_rbtree<K: Pod, V: Pod>(
+        &self,
+        root: usize,
+        layout: RbTreeLayout,
+    ) -> Result<Vec<(K, V)>, MemoryReadError> {
+        collections::read_rbtree(self, root, &layout)
+    }
+
+    /// Records every `alignment`-aligned pointer-sized slot of readable memory that points into
+    /// readable memory, so pointer paths can be searched offline.
+    ///
+    /// An `alignment` of 0 fails with `InvalidLength`.
+    fn build_pointer_map(&self, alignment: usize) -> Result<PointerMap, MemoryReadError> {
+        PointerMap::build(self, alignment)
+    }
+
+    /// Reads the elements of a span laid out as a pointer followed by a pointer-sized length.
+    fn read_span<T: Pod>(&self, span_address: usize) -> Result<Vec<T>, MemoryReadError> {
+        collections::read_span(self, span_address, &SpanLayout::default())
+    }
+
+    /// Reads the elements of a span whose fields are placed as described by the layout.
+    fn read_span_with_layout<T: Pod>(
+        &self,
+        span_address: usize,
+        layout: SpanLayout,
+    ) -> Result<Vec<T>, MemoryReadError> {
+        collections::read_span(self, span_address, &layout)
+    }
+
+    /// Reads Rust `Vec<T>` laid out as `{ptr, cap, len}`.
+    fn read_rust_vec<T: Pod>(&self, address: usize) -> Result<Vec<T>, MemoryReadError> {
+        let (ptr, cap, len) = read_rust_vec_parts(self, address)?;
+
+        if len > cap {
+            return Err(MemoryReadError::InvalidLength {
+                length: len,
+                max: cap,
+            });
+        }
+
+        let max_cap = isize::MAX as usize / size_of::<T>().max(1);
+
+        if cap > max_cap {
+            return Err(MemoryReadError::InvalidLength {
+                length: cap,
+                max: max_cap,
+            });
+        }
+
+        check_read_size(len * size_of::<T>(), self.max_read_size())?;
+
+        let mut values = vec![pod::zeroed::<T>(); len];
+
+        if len > 0 {
+            self.read_into_slice(ptr, &mut values)?;
+        }
+
+        Ok(values)
+    }
+
+    /// Reads Rust `String` laid out as `{ptr, cap, len}`.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    fn read_rust_string(&self, address: usize) -> Result<String, MemoryReadError> {
+        let bytes = self.read_rust_vec::<u8>(address)?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads `u64` until `reads` consecutive reads agree, guarding against torn values of a
+    /// concurrently updated counter.
+    ///
+    /// After `STABLE_READ_ATTEMPTS` times `reads` reads without agreement the most frequently
+    /// read value is returned.
+    fn read_u64_stable(&self, address: usize, reads: usize) -> Result<u64, MemoryReadError> {
+        let reads = reads.max(1);
+        let mut counts: Vec<(u64, usize)> = Vec::new();
+        let mut previous = None;
+        let mut streak = 0;
+
+        for _ in 0..reads.saturating_mul(STABLE_READ_ATTEMPTS) {
+            let value = self.read_u64(address)?;
+            streak = if previous == Some(value) {
+                streak + 1
+            } else {
+                1
+            };
+
+            if streak == reads {
+                return Ok(value);
+            }
+
+            previous = Some(value);
+
+            match counts.iter_mut().find(|(counted, _)| *counted == value) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+
+        // Ties go to the value whose first read came last.
+        Ok(counts
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .map_or(0, |(value, _)| value))
+    }
+
+    /// Reads `u64` stored as two `u32` words that are updated one after another, such as a
+    /// counter on a 32-bit target.
+    ///
+    /// The high word is read before and after the low word until both reads agree, so a carry
+    /// into the high word can't tear the value. After `SPLIT_READ_ATTEMPTS` rounds without
+    /// agreement the last low word is combined with the last high word.
+    fn read_u64_split_consistent(
+        &self,
+        low_address: usize,
+        high_address: usize,
+    ) -> Result<u64, MemoryReadError> {
+        let mut high = self.read_u32(high_address)?;
+        let mut low = 0;
+
+        for _ in 0..SPLIT_READ_ATTEMPTS {
+            low = self.read_u32(low_address)?;
+            let high_again = self.read_u32(high_address)?;
+
+            if high_again == high {
+                break;
+            }
+
+            high = high_again;
+        }
+
+        Ok((high as u64) << 32 | low as u64)
+    }
+
+    define_number_read!(u16, read_u16, read_u16_with_order, 2);
+    define_number_read!(i16, read_i16, read_i16_with_order, 2);
+    define_number_read!(u32, read_u32, read_u32_with_order, 4);
+    define_number_read!(u64, read_u64, read_u64_with_order, 8);
+    define_number_read!(u128, read_u128, read_u128_with_order, 16);
+    define_number_read!(i32, read_i32, read_i32_with_order, 4);
+    define_number_read!(i64, read_i64, read_i64_with_order, 8);
+    define_number_read!(f32, read_f32, read_f32_with_order, 4);
+    define_number_read!(f64, read_f64, read_f64_with_order, 8);
+
+    define_deref_read!(u32, read_u32_deref, read_u32);
+    define_deref_read!(u64, read_u64_deref, read_u64);
+    define_deref_read!(u128, read_u128_deref, read_u128);
+    define_deref_read!(i32, read_i32_deref, read_i32);
+    define_deref_read!(i64, read_i64_deref, read_i64);
+    define_deref_read!(f32, read_f32_deref, read_f32);
+    define_deref_read!(f64, read_f64_deref, read_f64);
+}
+
+/// Process whose memory can also be written.
+pub trait ProcessWriter: Process {
+    /// Writes all of `bytes` to the address memory.
+    fn write_bytes(&self, address: usize, bytes: &[u8]) -> Result<(), MemoryWriteError>;
+
+    /// Writes every `(address, bytes)` pair, in order.
+    ///
+    /// Platform processes batch the writes into as few calls as they can. Writing stops at the
+    /// first failure, leaving the earlier writes applied.
+    fn write_many(&self, writes: &[(usize, &[u8])]) -> Result<(), MemoryWriteError> {
+        for &(address, bytes) in writes {
+            self.write_bytes(address, bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies `len` bytes from `src` to `dst` within the process, like `memmove`.
+    ///
+    /// Bytes are copied in chunks, back to front when `dst` overlaps the end of the source, so
+    /// overlapping ranges end up as if copied at once. A failure leaves the earlier chunks
+    /// copied.
+    fn copy_within(&self, src: usize, dst: usize, len: usize) -> Result<(), MemoryWriteError> {
+        offset_address(src, len)?;
+        offset_address(dst, len)?;
+
+        let chunk_size = SCAN_CHUNK_SIZE.min(self.max_read_size()).max(1);
+        let mut buffer = vec![0u8; chunk_size.min(len)];
+        let mut copy_chunk = |offset: usize| {
+            let chunk = &mut buffer[..chunk_size.min(len - offset)];
+            self.read_bytes(src + offset, chunk)?;
+            self.write_bytes(dst + offset, chunk)
+        };
+
+        if dst > src && dst - src < len {
+            for offset in (0..len).step_by(chunk_size).rev() {
+                copy_chunk(offset)?;
+            }
+        } else {
+            for offset in (0..len).step_by(chunk_size) {
+                copy_chunk(offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_u8(&self, address: usize, value: u8) -> Result<(), MemoryWriteError> {
+        self.write_bytes(address, &[value])
     }
 
-    define_number_read!(u32, read_u32, 4);
-    define_number_read!(u64, read_u64, 8);
-    define_number_read!(u128, read_u128, 16);
-    define_number_read!(i32, read_i32, 4);
-    define_number_read!(i64, read_i64, 8);
-    define_number_read!(f32, read_f32, 4);
-    define_number_read!(f64, read_f64, 8);
+    define_number_write!(u32, write_u32, write_u32_with_order);
+    define_number_write!(u64, write_u64, write_u64_with_order);
+    define_number_write!(i32, write_i32, write_i32_with_order);
+    define_number_write!(f32, write_f32, write_f32_with_order);
+    define_number_write!(f64, write_f64, write_f64_with_order);
 }
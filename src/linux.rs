@@ -1,69 +1,786 @@
-use crate::{MemoryReadError, Process};
-use libc::{iovec, process_vm_readv};
-use std::fs::{read_dir, File};
-use std::io::{Error as IoError, BufReader, BufRead};
+use crate::region::RegionCache;
+#[cfg(feature = "metrics")]
+use crate::stats::ReadCounters;
+use crate::throttle::RateLimiter;
+#[cfg(feature = "metrics")]
+use crate::ReadStats;
+use crate::{
+    as_uninit_mut, assume_init_mut, check_pointer_size, check_read_size, is_aligned_pointer,
+    offset_address, Architecture, CachePolicy, Capabilities, Capability, Endianness,
+    MemoryReadError, MemoryRegion, MemoryUsage, MemoryWriteError, Module, OpenProcessError,
+    Process, ProcessEntry, ProcessState, ProcessStatus, ProcessWriter, RegionKind,
+    DEFAULT_MAX_READ_SIZE, PAGE_SIZE,
+};
+use libc::{
+    __errno_location, c_int, c_long, c_ulong, c_void, iovec, off_t, pid_t, poll, pollfd, pread,
+    process_vm_readv, process_vm_writev, ptrace, siginfo_t, syscall, sysconf, waitid, waitpid,
+    SYS_pidfd_open, _SC_PAGESIZE, __WALL, CLD_EXITED, EACCES, EFAULT, EIO, ENOSYS, EPERM, POLLIN,
+    PTRACE_ATTACH, PTRACE_DETACH, PTRACE_PEEKDATA, P_PID, WEXITED, WNOHANG, WNOWAIT,
+};
+use std::collections::HashMap;
+use std::fs::{read, read_dir, read_link, read_to_string, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Error as IoError, ErrorKind, Read};
+use std::mem::{size_of, MaybeUninit};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::{Mutex, OnceLock, PoisonError};
 
 /// Opens process with specified id.
 ///
 /// If the process is not found or could not be opened `None` will be returned.
 pub fn open_process(pid: u32) -> Option<LinuxProcess> {
-    Some(LinuxProcess { pid })
+    open_process_checked(pid).ok()
+}
+
+/// Opens process with specified id, telling why it could not be opened.
+///
+/// The process must have a `/proc` entry whose memory maps this process is allowed to read.
+pub fn open_process_checked(pid: u32) -> Result<LinuxProcess, OpenProcessError> {
+    let proc_dir = PathBuf::from(format!("/proc/{}", pid));
+
+    if let Err(error) = File::open(proc_dir.join("maps")) {
+        trace_event!(debug, pid, %error, "failed to open process");
+        return Err(OpenProcessError::from_io_error(pid, error));
+    }
+
+    Ok(open_with_proc_dir(pid, proc_dir))
+}
+
+/// Opens process `pid`, whose `/proc` entry is `proc_dir`.
+fn open_with_proc_dir(pid: u32, proc_dir: PathBuf) -> LinuxProcess {
+    trace_event!(debug, pid, "opened process");
+
+    LinuxProcess {
+        pid,
+        start_time: start_time(&proc_dir),
+        proc_dir,
+        max_read_size: DEFAULT_MAX_READ_SIZE,
+        endianness: Endianness::default(),
+        regions: RegionCache::default(),
+        #[cfg(feature = "metrics")]
+        read_counters: ReadCounters::default(),
+        read_strategy: Mutex::new(ReadStrategy::default()),
+        mem_file: OnceLock::new(),
+        pointer_size: OnceLock::new(),
+        rate_limiter: None,
+        pidfd: open_pidfd(pid),
+    }
+}
+
+/// Translates `pid` listed by the `/proc` root `proc_root`, such as the `/proc` of a container,
+/// into the pid the process has in this process' namespace.
+///
+/// Processes are matched by their pid in every namespace they are in, read from the `NSpid`
+/// status field, and their start time.
+pub fn host_pid<P: AsRef<Path>>(proc_root: P, pid: u32) -> Option<u32> {
+    let ns_start_time = start_time(&proc_root.as_ref().join(pid.to_string()))?;
+    let pid = pid.to_string();
+
+    all_pids().ok()?.into_iter().find(|&host_pid| {
+        let proc_dir = PathBuf::from(format!("/proc/{}", host_pid));
+        let status = match read_to_string(proc_dir.join("status")) {
+            Ok(status) => status,
+            Err(_) => return false,
+        };
+
+        parse_status_field(&status, "NSpid")
+            .is_some_and(|pids| pids.split_whitespace().any(|ns_pid| ns_pid == pid))
+            && start_time(&proc_dir) == Some(ns_start_time)
+    })
+}
+
+/// Reads `/proc/<pid>/stat` fields following the command name, the process state first.
+fn stat_fields(proc_dir: &Path) -> Option<Vec<String>> {
+    let stat = read_to_string(proc_dir.join("stat")).ok()?;
+
+    // The command name in parentheses may contain spaces, fields after it can't.
+    Some(
+        stat.rsplit_once(')')?
+            .1
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect(),
+    )
+}
+
+/// Boot-relative start time of the process, which tells a reused pid apart.
+fn start_time(proc_dir: &Path) -> Option<u64> {
+    // `starttime` is field 22 of the stat line, the state being field 3.
+    stat_fields(proc_dir)?.get(19)?.parse().ok()
+}
+
+/// Opens pidfd that keeps referring to the process after its pid is reused.
+fn open_pidfd(pid: u32) -> Option<OwnedFd> {
+    let fd = unsafe { syscall(SYS_pidfd_open, pid as pid_t, 0) };
+
+    if fd < 0 {
+        return None;
+    }
+
+    Some(unsafe { OwnedFd::from_raw_fd(fd as c_int) })
+}
+
+/// Lists ids of all running processes.
+pub fn all_pids() -> Result<Vec<u32>, IoError> {
+    let paths = read_dir("/proc")?;
+
+    Ok(paths
+        .flatten()
+        .filter_map(|path| path.file_name().to_str()?.parse::<u32>().ok())
+        .collect())
+}
+
+/// Lists running processes named after their executable, or `comm` when it can't be resolved.
+pub(crate) fn process_entries() -> Result<Vec<ProcessEntry>, IoError> {
+    Ok(all_pids()?
+        .into_iter()
+        .filter_map(|pid| {
+            let name = match read_link(format!("/proc/{}/exe", pid)) {
+                Ok(exe) => exe.file_name()?.to_string_lossy().into_owned(),
+                Err(_) => read_to_string(format!("/proc/{}/comm", pid))
+                    .ok()?
+                    .trim_end()
+                    .to_owned(),
+            };
+
+            Some(ProcessEntry { pid, name })
+        })
+        .collect())
 }
 
 /// Finds all processes with matching name.
+///
+/// The name is the file name of the executable, or `comm` when the executable can't be
+/// resolved. Processes that can't be inspected or opened are skipped.
 pub fn find_by_name(name: &str) -> Result<Vec<LinuxProcess>, IoError> {
-    let paths = read_dir("/proc")?;
-    let mut processes = vec![];
+    Ok(process_entries()?
+        .into_iter()
+        .filter(|entry| entry.name == name)
+        .filter_map(|entry| open_process(entry.pid))
+        .collect())
+}
 
-    for path_entry in paths {
-        if let Ok(path) = path_entry {
-            let maps_path = path.path().join("maps");
+/// Finds processes whose executable path, the target of `/proc/<pid>/exe`, contains `path`.
+///
+/// A full path selects one install; a part such as a directory selects all under it.
+pub fn find_by_path(path: &str) -> Result<Vec<LinuxProcess>, IoError> {
+    Ok(all_pids()?
+        .into_iter()
+        .filter(|pid| {
+            read_link(format!("/proc/{}/exe", pid))
+                .is_ok_and(|exe| exe.to_string_lossy().contains(path))
+        })
+        .filter_map(open_process)
+        .collect())
+}
 
-            if let Ok(file) = File::open(maps_path) {
-                let mut reader = BufReader::new(file);
-                let mut buffer = String::new();
-                reader.read_line(&mut buffer)?;
+/// Mechanism `LinuxProcess` reads memory with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReadStrategy {
+    /// `process_vm_readv`, a single syscall per read.
+    #[default]
+    ProcessVmReadv,
+    /// `pread` on `/proc/<pid>/mem`.
+    ProcMem,
+    /// `PTRACE_PEEKDATA` word by word, stopping the process while reading.
+    Ptrace,
+    /// Tries the backends above in order and keeps the first one the system allows.
+    Auto,
+    /// `PTRACE_PEEKDATA` word by word through a ptrace attach the caller already holds.
+    ///
+    /// The process is neither attached nor detached, so it must already be stopped and reads
+    /// have to come from the thread that attached it. Used by `LinuxProcess::from_ptrace`.
+    PtraceAttached,
+}
 
-                if buffer.trim().ends_with(name) {
-                    if let Some(pid_name) = path.file_name().to_str() {
-                        let pid = pid_name.parse::<u32>().unwrap();
+/// Most iovecs a single `process_vm_readv` or `process_vm_writev` call accepts (`UIO_MAXIOV`).
+const MAX_IOVECS: usize = 1024;
 
-                        open_process(pid).map(|process| processes.push(process));
-                    }
-                }
-            }
-        }
+/// Checks whether a read failed because the backend is unavailable rather than the address.
+fn is_unsupported_backend(error: &IoError) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(ENOSYS) | Some(EPERM) | Some(EACCES)
+    )
+}
+
+/// Checks once whether the kernel provides `process_vm_writev`.
+fn process_vm_writev_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+
+    // Empty writes return before any permission check, so only a missing syscall fails.
+    *SUPPORTED.get_or_init(|| {
+        let result = unsafe { process_vm_writev(0, ptr::null(), 0, ptr::null(), 0, 0) };
+
+        result != -1 || IoError::last_os_error().raw_os_error() != Some(ENOSYS)
+    })
+}
+
+/// Parses `/proc/<pid>/maps` line into the region and its pathname column.
+fn parse_maps_line(line: &str) -> Option<(MemoryRegion, &str)> {
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let permissions = fields.next()?.as_bytes();
+    let pathname = fields.nth(3).unwrap_or("").trim_start();
+
+    let base = usize::from_str_radix(start, 16).ok()?;
+    let end = usize::from_str_radix(end, 16).ok()?;
+
+    let region = MemoryRegion {
+        base,
+        size: end.saturating_sub(base),
+        readable: permissions.first() == Some(&b'r'),
+        writable: permissions.get(1) == Some(&b'w'),
+        executable: permissions.get(2) == Some(&b'x'),
+        kind: region_kind(pathname),
+    };
+
+    Some((region, pathname))
+}
+
+/// Classifies region by the `/proc/<pid>/maps` pathname column.
+fn region_kind(pathname: &str) -> RegionKind {
+    match pathname {
+        "" => RegionKind::Anonymous,
+        "[heap]" => RegionKind::Heap,
+        "[vdso]" => RegionKind::Vdso,
+        "[stack]" => RegionKind::Stack,
+        _ if pathname.starts_with("[stack:") => RegionKind::Stack,
+        // Anonymous mappings named with `PR_SET_VMA_ANON_NAME`.
+        _ if pathname.starts_with("[anon:") => RegionKind::Anonymous,
+        _ if pathname.starts_with('[') => RegionKind::Special(pathname.to_owned()),
+        _ => RegionKind::File(PathBuf::from(pathname.trim_end_matches(" (deleted)"))),
+    }
+}
+
+/// Reads value of `/proc/<pid>/status` field.
+fn parse_status_field<'a>(status: &'a str, key: &str) -> Option<&'a str> {
+    let line = status.lines().find(|line| line.starts_with(key))?;
+
+    Some(line[key.len()..].strip_prefix(':')?.trim())
+}
+
+/// Reads `/proc/<pid>/status` field given in kB as bytes.
+fn parse_status_bytes(status: &str, key: &str) -> Option<usize> {
+    let value = parse_status_field(status, key)?;
+    let kilobytes = value.trim_end_matches("kB").trim().parse::<usize>().ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+/// Maps ELF header `e_machine` to the architecture.
+fn architecture_from_elf_header(header: &[u8; 20]) -> Option<Architecture> {
+    if header[..4] != *b"\x7fELF" {
+        return None;
     }
 
-    Ok(processes)
+    let machine = [header[18], header[19]];
+
+    // `EI_DATA` is 2 for big-endian files.
+    let machine = match header[5] {
+        2 => u16::from_be_bytes(machine),
+        _ => u16::from_le_bytes(machine),
+    };
+
+    match machine {
+        3 => Some(Architecture::X86),
+        40 => Some(Architecture::Arm),
+        62 => Some(Architecture::X86_64),
+        183 => Some(Architecture::Aarch64),
+        _ => None,
+    }
 }
 
 #[derive(Debug)]
 pub struct LinuxProcess {
     pub pid: u32,
+    /// `/proc` entry of the process, which is in another namespace for `with_proc_root`.
+    proc_dir: PathBuf,
+    max_read_size: usize,
+    endianness: Endianness,
+    regions: RegionCache,
+    read_strategy: Mutex<ReadStrategy>,
+    mem_file: OnceLock<File>,
+    /// Pointer size of the executable, read from its ELF header on first use.
+    pointer_size: OnceLock<usize>,
+    rate_limiter: Option<RateLimiter>,
+    start_time: Option<u64>,
+    pidfd: Option<OwnedFd>,
+    #[cfg(feature = "metrics")]
+    read_counters: ReadCounters,
 }
 
-impl Process for LinuxProcess {
-    fn base_address(&self, module_name: &str) -> Option<usize> {
-        let file_name = format!("/proc/{}/maps", self.pid);
+impl PartialEq for LinuxProcess {
+    fn eq(&self, other: &Self) -> bool {
+        self.pid == other.pid
+    }
+}
+
+impl Eq for LinuxProcess {}
+
+impl Hash for LinuxProcess {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pid.hash(state);
+    }
+}
+
+impl LinuxProcess {
+    /// Opens process the caller has already attached to and stopped with `PTRACE_ATTACH`,
+    /// reading through that attach with `ReadStrategy::PtraceAttached`.
+    ///
+    /// If the process is not found or could not be opened `None` will be returned.
+    pub fn from_ptrace(pid: u32) -> Option<Self> {
+        let mut process = open_process(pid)?;
+        process.set_read_strategy(ReadStrategy::PtraceAttached);
+
+        Some(process)
+    }
+
+    /// Opens process listed as `pid` by the `/proc` root `proc_root`, such as the `/proc` of a
+    /// container whose pid namespace differs from this process'.
+    ///
+    /// Files such as `maps` are read from `proc_root`, while syscalls use the pid `host_pid`
+    /// resolves, which `pid()` returns. If the process is not found `None` will be returned.
+    pub fn with_proc_root<P: AsRef<Path>>(pid: u32, proc_root: P) -> Option<Self> {
+        let host_pid = host_pid(&proc_root, pid)?;
+
+        Some(open_with_proc_dir(
+            host_pid,
+            proc_root.as_ref().join(pid.to_string()),
+        ))
+    }
+
+    /// Id of the process.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Short name of the process from `comm`, at most 15 bytes long.
+    pub fn name(&self) -> Option<String> {
+        let comm = read_to_string(self.proc_dir.join("comm")).ok()?;
+
+        Some(comm.trim_end().to_owned())
+    }
+
+    /// Full path of the executable, the target of `/proc/<pid>/exe`.
+    pub fn exe_path(&self) -> Option<PathBuf> {
+        read_link(self.proc_dir.join("exe")).ok()
+    }
+
+    /// Sets the largest amount of bytes a single `read_bytes` call may request.
+    pub fn set_max_read_size(&mut self, max_read_size: usize) {
+        self.max_read_size = max_read_size;
+    }
+
+    /// Limits reads to at most `reads_per_second`, sleeping before a read when needed.
+    ///
+    /// `None` or 0 removes the limit.
+    pub fn set_rate_limit(&mut self, reads_per_second: Option<u32>) {
+        self.rate_limiter = reads_per_second.and_then(RateLimiter::new);
+    }
+
+    /// Reads per second the process is limited to, if any.
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limiter
+            .as_ref()
+            .map(RateLimiter::reads_per_second)
+    }
+
+    /// Sets how long the region list `is_plausible_pointer` checks against is cached.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.regions.set_policy(policy);
+    }
+
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.regions.policy()
+    }
+
+    /// Reloads the region list `is_plausible_pointer` checks against.
+    pub fn refresh_regions(&self) -> Result<(), MemoryReadError> {
+        self.regions.refresh(|| self.memory_regions())
+    }
+
+    /// Counters of the reads made so far.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> ReadStats {
+        self.read_counters.stats()
+    }
+
+    /// Zeroes the read counters.
+    #[cfg(feature = "metrics")]
+    pub fn reset_stats(&self) {
+        self.read_counters.reset();
+    }
+
+    /// Sets the byte order the number readers decode with.
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Overrides the detected size of the pointers `read_ptr` and the pointer chains read.
+    ///
+    /// Fails with `InvalidFormat` unless the size is 4 or 8.
+    pub fn set_pointer_size(&mut self, pointer_size: usize) -> Result<(), MemoryReadError> {
+        check_pointer_size(pointer_size)?;
+        self.pointer_size = OnceLock::from(pointer_size);
+
+        Ok(())
+    }
+
+    /// Sets the mechanism memory is read with.
+    pub fn set_read_strategy(&mut self, strategy: ReadStrategy) {
+        *self
+            .read_strategy
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = strategy;
+    }
+
+    /// Mechanism memory is read with.
+    ///
+    /// With `Auto` this is the backend picked by the first read, or `Auto` before any read.
+    pub fn read_strategy(&self) -> ReadStrategy {
+        *self
+            .read_strategy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Backends the kernel provides for the process.
+    ///
+    /// Reads always have a backend; writes need `process_vm_writev`. Whether the caller may
+    /// access the process is only known once it tries.
+    pub fn capabilities(&self) -> Capabilities {
+        let write = process_vm_writev_supported();
+
+        Capabilities {
+            read: true,
+            write,
+            operation: write,
+        }
+    }
+
+    /// Releases the process. Nothing is held open on Linux, so this never fails.
+    pub fn close(self) -> Result<(), MemoryReadError> {
+        Ok(())
+    }
+
+    /// Instruction set of the executable, read from its ELF header.
+    pub fn architecture(&self) -> Option<Architecture> {
+        architecture_from_elf_header(&self.exe_header()?)
+    }
+
+    fn exe_header(&self) -> Option<[u8; 20]> {
+        let mut file = File::open(self.proc_dir.join("exe")).ok()?;
+        let mut header = [0u8; 20];
+        file.read_exact(&mut header).ok()?;
+
+        Some(header)
+    }
+
+    /// Reads the stack of thread `tid`.
+    ///
+    /// Only stacks named in `/proc/<pid>/maps` can be found, which is the main thread's `[stack]`
+    /// mapping (and `[stack:<tid>]` mappings on older kernels).
+    pub fn read_thread_stack(&self, tid: u32) -> Result<Vec<u8>, MemoryReadError> {
+        let thread_stack = format!("[stack:{}]", tid);
+        let maps = self.read_maps()?;
+
+        let (stack, _) = maps
+            .lines()
+            .filter_map(parse_maps_line)
+            .find(|&(_, pathname)| {
+                pathname == thread_stack || (tid == self.pid && pathname == "[stack]")
+            })
+            .ok_or_else(|| IoError::new(ErrorKind::NotFound, "thread stack mapping not found"))?;
+
+        self.read_bytes_vec(stack.base, stack.size)
+    }
+
+    /// Lists ids of the threads of the process, read from `/proc/<pid>/task`.
+    ///
+    /// Linux does not record thread start addresses, so there is no `thread_start_address`.
+    pub fn thread_ids(&self) -> Result<Vec<u32>, MemoryReadError> {
+        let tasks = read_dir(self.proc_dir.join("task"))?;
+
+        Ok(tasks
+            .flatten()
+            .filter_map(|task| task.file_name().to_str()?.parse::<u32>().ok())
+            .collect())
+    }
+
+    /// Command-line arguments of the process, read from `/proc/<pid>/cmdline`.
+    ///
+    /// Kernel threads and zombies have no arguments, so an empty list is returned for them.
+    pub fn argv(&self) -> Result<Vec<String>, MemoryReadError> {
+        let cmdline = read(self.proc_dir.join("cmdline"))?;
+        let cmdline = cmdline.strip_suffix(&[0]).unwrap_or(&cmdline);
+
+        if cmdline.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(cmdline
+            .split(|&byte| byte == 0)
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect())
+    }
+
+    /// Current memory footprint, read from `/proc/<pid>/status`.
+    pub fn memory_usage(&self) -> Result<MemoryUsage, MemoryReadError> {
+        let status = read_to_string(self.proc_dir.join("status"))?;
+        let field = |key| parse_status_bytes(&status, key).unwrap_or(0);
+
+        Ok(MemoryUsage {
+            working_set: field("VmRSS"),
+            peak_working_set: field("VmHWM"),
+            private_bytes: field("RssAnon"),
+        })
+    }
+
+    /// Parses `/proc/<pid>/status`.
+    ///
+    /// Missing fields, such as the memory sizes of kernel threads, are left zeroed.
+    pub fn status(&self) -> Result<ProcessStatus, MemoryReadError> {
+        let status = read_to_string(self.proc_dir.join("status"))?;
+        let field = |key| parse_status_field(&status, key);
+        // Uid and Gid list the real, effective, saved and filesystem ids.
+        let first_number = |key| field(key)?.split_whitespace().next()?.parse().ok();
+
+        Ok(ProcessStatus {
+            name: field("Name").unwrap_or_default().to_owned(),
+            state: field("State")
+                .and_then(|state| state.chars().next())
+                .map(ProcessState::from),
+            ppid: field("PPid")
+                .and_then(|ppid| ppid.parse().ok())
+                .unwrap_or(0),
+            uid: first_number("Uid"),
+            gid: first_number("Gid"),
+            vm_rss: parse_status_bytes(&status, "VmRSS").unwrap_or(0),
+            vm_size: parse_status_bytes(&status, "VmSize").unwrap_or(0),
+            threads: field("Threads")
+                .and_then(|threads| threads.parse().ok())
+                .unwrap_or(0),
+        })
+    }
+
+    /// Checks that the pid still belongs to the process that was opened.
+    ///
+    /// A pid is only reused once its process is gone, so this is cheap while the pidfd reports
+    /// the process as running; afterwards the start time recorded at open is compared. Fails
+    /// with `ProcessChanged` when another process has the pid now, and with `ProcessNotFound`
+    /// once the process is gone. A start time unknown at open can't tell reuse apart.
+    ///
+    /// Reads only check it after failing, so long-running readers should call it, or
+    /// `is_alive`, between polls to notice reuse of the pid.
+    pub fn verify_identity(&self) -> Result<(), MemoryReadError> {
+        if let Some(pidfd) = &self.pidfd {
+            let mut poll_fd = pollfd {
+                fd: pidfd.as_raw_fd(),
+                events: POLLIN,
+                revents: 0,
+            };
+
+            // The pidfd turns readable once the process exits.
+            if unsafe { poll(&mut poll_fd, 1, 0) } == 0 {
+                return Ok(());
+            }
+        }
+
+        match (start_time(&self.proc_dir), self.start_time) {
+            (None, _) => Err(MemoryReadError::ProcessNotFound { pid: self.pid }),
+            (Some(start_time), Some(recorded)) if start_time != recorded => {
+                Err(MemoryReadError::ProcessChanged { pid: self.pid })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// State of the process, read from `/proc/<pid>/stat`, or `None` once it is gone.
+    pub fn state(&self) -> Option<ProcessState> {
+        let fields = stat_fields(&self.proc_dir)?;
+
+        fields.first()?.chars().next().map(ProcessState::from)
+    }
+
+    /// Checks that the opened process is still running under its pid.
+    ///
+    /// Returns `false` once it exited, including while it is a zombie whose `/proc` entry is
+    /// still there, and after its pid was reused by another process.
+    pub fn is_alive(&self) -> bool {
+        self.verify_identity().is_ok()
+            && !matches!(
+                self.state(),
+                None | Some(ProcessState::Zombie) | Some(ProcessState::Dead)
+            )
+    }
+
+    /// Exit code of the terminated process, or `None` while it is still running.
+    ///
+    /// Children are checked with `waitid` without reaping them. Other processes only report it
+    /// while they are zombies, so `None` is also returned once they are gone from `/proc`.
+    /// Processes killed by a signal report `128 + signal`.
+    pub fn exit_code(&self) -> Option<u32> {
+        let mut info = MaybeUninit::<siginfo_t>::zeroed();
+        let result = unsafe {
+            waitid(
+                P_PID,
+                self.pid,
+                info.as_mut_ptr(),
+                WEXITED | WNOHANG | WNOWAIT,
+            )
+        };
+
+        if result == 0 {
+            let info = unsafe { info.assume_init() };
+
+            // `WNOHANG` leaves the pid zeroed while the child is still running.
+            if unsafe { info.si_pid() } == 0 {
+                return None;
+            }
+
+            let status = unsafe { info.si_status() } as u32;
+
+            return Some(match info.si_code {
+                CLD_EXITED => status,
+                _ => 128 + status,
+            });
+        }
+
+        let fields = stat_fields(&self.proc_dir)?;
+
+        if !matches!(fields.first().map(String::as_str), Some("Z") | Some("X")) {
+            return None;
+        }
+
+        let status = fields.last()?.parse::<u32>().ok()?;
+
+        // Same encoding as the `waitpid` status.
+        Some(match status & 0x7f {
+            0 => (status >> 8) & 0xff,
+            signal => 128 + signal,
+        })
+    }
+
+    fn find_base_address(&self, module_name: &str) -> Option<usize> {
+        let file_name = self.proc_dir.join("maps");
         let file = File::open(file_name).ok()?;
         let reader = BufReader::new(file);
 
-        for result in reader.lines() {
-            if let Ok(line) = result {
-                if line.trim().ends_with(module_name) {
-                    let split_line: Vec<&str> = line.split("-").collect();
-                    let address_str = split_line[0];
+        // Modules are mapped as several regions, the lowest of which is the image base.
+        reader
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| {
+                let (region, pathname) = parse_maps_line(&line)?;
+                let path = Path::new(pathname.trim_end().trim_end_matches(" (deleted)"));
+
+                (path.file_name() == Some(module_name.as_ref()) || path == Path::new(module_name))
+                    .then_some(region.base)
+            })
+            .min()
+    }
+
+    fn read_maps(&self) -> Result<String, IoError> {
+        read_to_string(self.proc_dir.join("maps"))
+    }
+
+    fn read_process_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        // Empty reads are answered without asking the OS, which handles them inconsistently.
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait();
+        }
+
+        // Identity is only checked once a read fails, which keeps it off the hot path; reads
+        // succeeding after pid reuse are caught by `verify_identity` or `is_alive`. The read
+        // error is kept unless the process is gone or another one has its pid.
+        let result =
+            self.read_memory(address, buffer)
+                .map_err(|error| match self.verify_identity() {
+                    Ok(()) => error,
+                    Err(changed) => changed,
+                });
+
+        #[cfg(feature = "metrics")]
+        self.read_counters.record(&result);
+
+        result
+    }
+
+    fn read_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        check_read_size(buffer.len(), self.max_read_size)?;
+        offset_address(address, buffer.len())?;
+
+        let strategy = *self
+            .read_strategy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if strategy != ReadStrategy::Auto {
+            return self.read_with(strategy, address, buffer);
+        }
 
-                    return usize::from_str_radix(address_str, 16).ok();
+        let mut unsupported = None;
+
+        for strategy in [
+            ReadStrategy::ProcessVmReadv,
+            ReadStrategy::ProcMem,
+            ReadStrategy::Ptrace,
+        ] {
+            match self.read_with(strategy, address, buffer) {
+                Err(MemoryReadError::IOError { io_error }) if is_unsupported_backend(&io_error) => {
+                    unsupported = Some(io_error);
+                }
+                result => {
+                    trace_event!(debug, pid = self.pid, strategy = ?strategy, "selected read strategy");
+                    *self
+                        .read_strategy
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner) = strategy;
+
+                    return result;
                 }
             }
         }
 
-        None
+        Err(MemoryReadError::IOError {
+            io_error: unsupported.unwrap_or_else(|| IoError::from_raw_os_error(ENOSYS)),
+        })
     }
 
-    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+    fn read_with(
+        &self,
+        strategy: ReadStrategy,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        match strategy {
+            ReadStrategy::ProcMem => self.read_proc_mem(address, buffer),
+            ReadStrategy::Ptrace => self.read_ptrace(address, buffer),
+            ReadStrategy::PtraceAttached => self.peek_words(address, buffer),
+            _ => self.read_vm_readv(address, buffer),
+        }
+    }
+
+    fn read_vm_readv(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
         let local_iov = iovec {
             iov_base: buffer.as_mut_ptr() as *mut _,
             iov_len: buffer.len(),
@@ -77,12 +794,362 @@ impl Process for LinuxProcess {
         let result = unsafe { process_vm_readv(self.pid as i32, &local_iov, 1, &remote_iov, 1, 0) };
 
         if result == -1 {
-            return Err(MemoryReadError::IOError {
-                io_error: IoError::last_os_error(),
+            let io_error = IoError::last_os_error();
+
+            // Unmapped addresses fail with `EFAULT`.
+            if io_error.raw_os_error() == Some(EFAULT) {
+                return Err(MemoryReadError::InaccessibleMemoryAddress {
+                    address,
+                    os_error: Some(io_error),
+                });
+            }
+
+            return Err(MemoryReadError::IOError { io_error });
+        }
+
+        // Nothing read into a nonempty buffer means the remote address is unmapped.
+        if result == 0 && !buffer.is_empty() {
+            return Err(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: None,
             });
         }
 
-        let read = result as usize;
+        Ok(result as usize)
+    }
+
+    /// Reads the whole batch with a single `process_vm_readv` call.
+    ///
+    /// Returns `None` when another strategy is selected, the total length overflows or the call
+    /// comes short, so the requests are read one by one and the failing one is found.
+    fn read_vm_readv_batch(&self, batch: &[(usize, usize)]) -> Option<Vec<Vec<u8>>> {
+        let strategy = *self
+            .read_strategy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if !matches!(strategy, ReadStrategy::Auto | ReadStrategy::ProcessVmReadv) {
+            return None;
+        }
+
+        for &(address, len) in batch {
+            check_read_size(len, self.max_read_size).ok()?;
+            offset_address(address, len).ok()?;
+        }
+
+        let expected = batch
+            .iter()
+            .try_fold(0usize, |total, &(_, len)| total.checked_add(len))?;
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait();
+        }
+
+        let mut buffers: Vec<Vec<u8>> = batch
+            .iter()
+            .map(|&(_, len)| Vec::with_capacity(len))
+            .collect();
+
+        let local_iov: Vec<iovec> = buffers
+            .iter_mut()
+            .zip(batch)
+            .map(|(buffer, &(_, len))| iovec {
+                iov_base: buffer.spare_capacity_mut().as_mut_ptr() as *mut _,
+                iov_len: len,
+            })
+            .collect();
+
+        let remote_iov: Vec<iovec> = batch
+            .iter()
+            .map(|&(address, len)| iovec {
+                iov_base: address as *mut _,
+                iov_len: len,
+            })
+            .collect();
+
+        let result = unsafe {
+            process_vm_readv(
+                self.pid as i32,
+                local_iov.as_ptr(),
+                local_iov.len() as c_ulong,
+                remote_iov.as_ptr(),
+                remote_iov.len() as c_ulong,
+                0,
+            )
+        };
+        trace_event!(
+            trace,
+            pid = self.pid,
+            reads = batch.len(),
+            len = expected,
+            "read_many"
+        );
+
+        if result == -1 || result as usize != expected {
+            return None;
+        }
+
+        #[cfg(feature = "metrics")]
+        self.read_counters.record(&Ok(expected));
+
+        for (buffer, &(_, len)) in buffers.iter_mut().zip(batch) {
+            unsafe { buffer.set_len(len) };
+        }
+
+        Some(buffers)
+    }
+
+    fn read_proc_mem(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        let file = match self.mem_file.get() {
+            Some(file) => file,
+            None => {
+                let file = File::open(self.proc_dir.join("mem"))?;
+                self.mem_file.get_or_init(|| file)
+            }
+        };
+
+        let result = unsafe {
+            pread(
+                file.as_raw_fd(),
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len(),
+                address as off_t,
+            )
+        };
+
+        if result == -1 {
+            let io_error = IoError::last_os_error();
+
+            // Unmapped addresses fail with `EIO`.
+            if io_error.raw_os_error() == Some(EIO) {
+                return Err(MemoryReadError::InaccessibleMemoryAddress {
+                    address,
+                    os_error: Some(io_error),
+                });
+            }
+
+            return Err(io_error.into());
+        }
+
+        Ok(result as usize)
+    }
+
+    /// Attaches for the duration of the read and copies memory one word at a time.
+    fn read_ptrace(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        let pid = self.pid as pid_t;
+
+        if unsafe {
+            ptrace(
+                PTRACE_ATTACH,
+                pid,
+                ptr::null_mut::<c_void>(),
+                ptr::null_mut::<c_void>(),
+            )
+        } == -1
+        {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let mut status = 0;
+        unsafe { waitpid(pid, &mut status, __WALL) };
+
+        let result = self.peek_words(address, buffer);
+
+        unsafe {
+            ptrace(
+                PTRACE_DETACH,
+                pid,
+                ptr::null_mut::<c_void>(),
+                ptr::null_mut::<c_void>(),
+            )
+        };
+
+        result
+    }
+
+    /// Reads with `PTRACE_PEEKDATA` from the process the calling thread is attached to.
+    fn peek_words(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        let pid = self.pid as pid_t;
+        let word_size = size_of::<c_long>();
+        let mut read = 0;
+        let mut os_error = None;
+
+        while read < buffer.len() {
+            let word = unsafe {
+                *__errno_location() = 0;
+                ptrace(
+                    PTRACE_PEEKDATA,
+                    pid,
+                    (address + read) as *mut c_void,
+                    ptr::null_mut::<c_void>(),
+                )
+            };
+
+            if word == -1 && unsafe { *__errno_location() } != 0 {
+                os_error = Some(IoError::last_os_error());
+                break;
+            }
+
+            let len = word_size.min(buffer.len() - read);
+
+            for (byte, value) in buffer[read..read + len].iter_mut().zip(word.to_ne_bytes()) {
+                *byte = MaybeUninit::new(value);
+            }
+
+            read += len;
+        }
+
+        if read == 0 && !buffer.is_empty() {
+            return Err(MemoryReadError::InaccessibleMemoryAddress { address, os_error });
+        }
+
+        Ok(read)
+    }
+}
+
+impl Process for LinuxProcess {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        let base = self.find_base_address(module_name);
+        trace_event!(debug, pid = self.pid, module_name, base = ?base, "resolved module base address");
+
+        base
+    }
+
+    fn main_module_base(&self) -> Option<usize> {
+        let exe = read_link(self.proc_dir.join("exe")).ok()?;
+        let maps = self.read_maps().ok()?;
+
+        maps.lines()
+            .filter_map(parse_maps_line)
+            .find(|(_, pathname)| Path::new(pathname) == exe)
+            .map(|(region, _)| region.base)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        let maps = self.read_maps()?;
+
+        Ok(maps
+            .lines()
+            .filter_map(parse_maps_line)
+            .map(|(region, _)| region)
+            .collect())
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        let maps = self.read_maps()?;
+        let mut modules: Vec<Module> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+
+        for (region, pathname) in maps.lines().filter_map(parse_maps_line) {
+            let path = pathname.trim_end_matches(" (deleted)");
+
+            if !path.starts_with('/') {
+                continue;
+            }
+
+            match indices.get(path) {
+                Some(&index) => {
+                    let module = &mut modules[index];
+                    let end = module.end().max(region.end());
+                    module.base = module.base.min(region.base);
+                    module.size = end - module.base;
+                }
+                None => {
+                    indices.insert(path.to_owned(), modules.len());
+
+                    let path = Path::new(path);
+                    let name = path.file_name().unwrap_or_default();
+
+                    modules.push(Module {
+                        name: name.to_string_lossy().into_owned(),
+                        path: path.to_path_buf(),
+                        base: region.base,
+                        size: region.size,
+                    });
+                }
+            }
+        }
+
+        modules.sort_by_key(|module| module.base);
+
+        Ok(modules)
+    }
+
+    fn is_plausible_pointer(&self, value: usize) -> bool {
+        is_aligned_pointer(self, value) && self.regions.is_readable(value, || self.memory_regions())
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn max_read_size(&self) -> usize {
+        self.max_read_size
+    }
+
+    /// Pointer size of the ELF class of the executable, or of this process when the
+    /// executable can't be read, unless overridden with `set_pointer_size`.
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(*self.pointer_size.get_or_init(|| {
+            // `EI_CLASS` is 1 for 32-bit files, including x32 ones.
+            match self.exe_header() {
+                Some(header) if header[..4] == *b"\x7fELF" && header[4] == 1 => 4,
+                Some(header) if header[..4] == *b"\x7fELF" && header[4] == 2 => 8,
+                _ => size_of::<usize>(),
+            }
+        }))
+    }
+
+    fn page_size(&self) -> usize {
+        match unsafe { sysconf(_SC_PAGESIZE) } {
+            size if size > 0 => size as usize,
+            _ => PAGE_SIZE,
+        }
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        let result = self
+            .read_process_memory(address, as_uninit_mut(buffer))
+            .and_then(|read| {
+                if read != buffer.len() {
+                    return Err(MemoryReadError::LessBytesRead {
+                        expected: buffer.len(),
+                        actual: read,
+                    });
+                }
+
+                Ok(())
+            });
+        trace_event!(
+            trace,
+            pid = self.pid,
+            address,
+            len = buffer.len(),
+            error = ?result.as_ref().err(),
+            "read_bytes"
+        );
+
+        result
+    }
+
+    fn read_bytes_uninit<'a>(
+        &self,
+        address: usize,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], MemoryReadError> {
+        let read = self.read_process_memory(address, buffer)?;
 
         if read != buffer.len() {
             return Err(MemoryReadError::LessBytesRead {
@@ -91,6 +1158,293 @@ impl Process for LinuxProcess {
             });
         }
 
+        Ok(unsafe { assume_init_mut(buffer) })
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        self.read_process_memory(address, as_uninit_mut(buffer))
+    }
+
+    /// Reads every batch of up to `UIO_MAXIOV` requests with a single `process_vm_readv` call.
+    ///
+    /// Batches the call cannot read whole are read again request by request.
+    fn read_many(&self, requests: &[(usize, usize)]) -> Result<Vec<Vec<u8>>, MemoryReadError> {
+        let mut values = Vec::with_capacity(requests.len());
+
+        for (batch_index, batch) in requests.chunks(MAX_IOVECS).enumerate() {
+            if let Some(buffers) = self.read_vm_readv_batch(batch) {
+                values.extend(buffers);
+                continue;
+            }
+
+            for (index, &(address, len)) in batch.iter().enumerate() {
+                let value = self.read_bytes_vec(address, len).map_err(|read_error| {
+                    MemoryReadError::RequestFailed {
+                        index: batch_index * MAX_IOVECS + index,
+                        read_error: Box::new(read_error),
+                    }
+                })?;
+
+                values.push(value);
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+impl ProcessWriter for LinuxProcess {
+    fn write_bytes(&self, address: usize, bytes: &[u8]) -> Result<(), MemoryWriteError> {
+        self.write_many(&[(address, bytes)])
+    }
+
+    /// Writes the batch with a single `process_vm_writev` call per `UIO_MAXIOV` writes.
+    fn write_many(&self, writes: &[(usize, &[u8])]) -> Result<(), MemoryWriteError> {
+        if let Some(needed) = self.capabilities().missing(&[Capability::Write]) {
+            return Err(MemoryWriteError::MissingCapability { needed });
+        }
+
+        for batch in writes.chunks(MAX_IOVECS) {
+            let local_iov: Vec<iovec> = batch
+                .iter()
+                .map(|(_, bytes)| iovec {
+                    iov_base: bytes.as_ptr() as *mut _,
+                    iov_len: bytes.len(),
+                })
+                .collect();
+
+            let remote_iov: Vec<iovec> = batch
+                .iter()
+                .map(|&(address, bytes)| iovec {
+                    iov_base: address as *mut _,
+                    iov_len: bytes.len(),
+                })
+                .collect();
+
+            let expected = batch.iter().map(|(_, bytes)| bytes.len()).sum();
+
+            let result = unsafe {
+                process_vm_writev(
+                    self.pid as i32,
+                    local_iov.as_ptr(),
+                    local_iov.len() as c_ulong,
+                    remote_iov.as_ptr(),
+                    remote_iov.len() as c_ulong,
+                    0,
+                )
+            };
+            trace_event!(
+                trace,
+                pid = self.pid,
+                writes = batch.len(),
+                len = expected,
+                "write_many"
+            );
+
+            if result == -1 {
+                return Err(IoError::last_os_error().into());
+            }
+
+            if result as usize != expected {
+                return Err(MemoryWriteError::LessBytesWritten {
+                    expected,
+                    actual: result as usize,
+                });
+            }
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libc::{
+        mmap, mprotect, munmap, MAP_ANONYMOUS, MAP_FAILED, MAP_PRIVATE, PROT_NONE, PROT_READ,
+        PROT_WRITE,
+    };
+
+    /// Three pages of this process whose middle one is inaccessible, unmapped on drop.
+    struct GuardedPages {
+        address: usize,
+        page_size: usize,
+    }
+
+    impl GuardedPages {
+        fn new() -> Self {
+            let page_size = unsafe { sysconf(_SC_PAGESIZE) } as usize;
+            let address = unsafe {
+                mmap(
+                    ptr::null_mut(),
+                    3 * page_size,
+                    PROT_READ | PROT_WRITE,
+                    MAP_PRIVATE | MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            };
+            assert_ne!(address, MAP_FAILED);
+
+            unsafe {
+                ptr::write_bytes(address as *mut u8, 0xaa, 3 * page_size);
+                assert_eq!(mprotect(address.add(page_size), page_size, PROT_NONE), 0);
+            }
+
+            GuardedPages {
+                address: address as usize,
+                page_size,
+            }
+        }
+    }
+
+    impl Drop for GuardedPages {
+        fn drop(&mut self) {
+            unsafe { munmap(self.address as *mut c_void, 3 * self.page_size) };
+        }
+    }
+
+    #[test]
+    fn reads_part_before_inaccessible_page() {
+        let pages = GuardedPages::new();
+        let process = open_process(std::process::id()).unwrap();
+        let start = pages.address + pages.page_size - 16;
+        let mut buffer = [0u8; 64];
+
+        assert_eq!(process.read_bytes_partial(start, &mut buffer).unwrap(), 16);
+        assert!(buffer[..16].iter().all(|&byte| byte == 0xaa));
+        assert!(matches!(
+            process.read_bytes(start, &mut buffer),
+            Err(MemoryReadError::LessBytesRead {
+                expected: 64,
+                actual: 16
+            })
+        ));
+    }
+
+    #[test]
+    fn finds_lowest_base_of_exactly_named_module() {
+        let proc_dir = std::env::temp_dir().join(format!("maps-test-{}", std::process::id()));
+        std::fs::create_dir_all(&proc_dir).unwrap();
+        std::fs::write(
+            proc_dir.join("maps"),
+            "\
+7f0000000000-7f0000001000 r--p 00000000 08:01 10 /usr/lib/mylibc.so.6
+7f0000103000-7f0000104000 rw-p 00003000 08:01 11 /usr/lib/libc.so.6
+7f0000100000-7f0000101000 r--p 00000000 08:01 11 /usr/lib/libc.so.6
+7f0000101000-7f0000103000 r-xp 00001000 08:01 11 /usr/lib/libc.so.6
+7f0000200000-7f0000201000 r-xp 00000000 08:01 12 /opt/game/game (deleted)
+7ffd00000000-7ffd00021000 rw-p 00000000 00:00 0  [stack]
+",
+        )
+        .unwrap();
+
+        let process = open_with_proc_dir(std::process::id(), proc_dir.clone());
+
+        assert_eq!(process.base_address("libc.so.6"), Some(0x7f00_0010_0000));
+        assert_eq!(
+            process.base_address("/usr/lib/libc.so.6"),
+            Some(0x7f00_0010_0000)
+        );
+        assert_eq!(process.base_address("game"), Some(0x7f00_0020_0000));
+        assert_eq!(process.base_address("libc"), None);
+        assert_eq!(process.base_address("c.so.6"), None);
+
+        std::fs::remove_dir_all(proc_dir).unwrap();
+    }
+
+    fn write_stat(proc_dir: &Path, start_time: u64) {
+        let fields = vec!["0"; 18].join(" ");
+
+        std::fs::write(
+            proc_dir.join("stat"),
+            format!("1 (name with ) paren) S {} {}\n", fields, start_time),
+        )
+        .unwrap();
+    }
+
+    fn fake_process(name: &str) -> (LinuxProcess, PathBuf) {
+        let proc_dir = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&proc_dir).unwrap();
+        write_stat(&proc_dir, 100);
+
+        let mut process = open_with_proc_dir(std::process::id(), proc_dir.clone());
+        // Without the pidfd the start time is compared on every check.
+        process.pidfd = None;
+
+        (process, proc_dir)
+    }
+
+    #[test]
+    fn tells_changed_process_from_exited_one() {
+        let (process, proc_dir) = fake_process("identity-test");
+        assert!(process.verify_identity().is_ok());
+
+        write_stat(&proc_dir, 200);
+        assert!(matches!(
+            process.verify_identity(),
+            Err(MemoryReadError::ProcessChanged { .. })
+        ));
+
+        std::fs::remove_dir_all(&proc_dir).unwrap();
+        assert!(matches!(
+            process.verify_identity(),
+            Err(MemoryReadError::ProcessNotFound { .. })
+        ));
+        assert!(matches!(
+            process.read_u8(0),
+            Err(MemoryReadError::ProcessNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn keeps_read_error_when_start_time_was_unknown() {
+        let (mut process, proc_dir) = fake_process("unknown-start-test");
+        process.start_time = None;
+
+        assert!(process.verify_identity().is_ok());
+        assert!(!matches!(
+            process.read_u8(0),
+            Err(MemoryReadError::ProcessChanged { .. } | MemoryReadError::ProcessNotFound { .. })
+        ));
+
+        std::fs::remove_dir_all(proc_dir).unwrap();
+    }
+
+    #[test]
+    fn builder_applies_options() {
+        let process = LinuxProcess::builder(std::process::id())
+            .with_endianness(Endianness::Big)
+            .with_pointer_size(4)
+            .with_rate_limit(0)
+            .build()
+            .unwrap();
+
+        assert_eq!(process.endianness(), Endianness::Big);
+        assert_eq!(process.pointer_size().unwrap(), 4);
+        assert_eq!(process.rate_limit(), None);
+
+        assert!(LinuxProcess::builder(std::process::id())
+            .with_pointer_size(0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_pointer_sizes() {
+        let mut process = LinuxProcess::builder(std::process::id()).build().unwrap();
+
+        for pointer_size in [0, 1, 2, 16] {
+            assert!(matches!(
+                process.set_pointer_size(pointer_size),
+                Err(MemoryReadError::InvalidFormat { .. })
+            ));
+        }
+        process.set_pointer_size(4).unwrap();
+        assert_eq!(process.pointer_size().unwrap(), 4);
+    }
+}
@@ -0,0 +1,675 @@
+use crate::nsstring::{self, TaggedPointers};
+use crate::region::RegionCache;
+#[cfg(feature = "metrics")]
+use crate::stats::ReadCounters;
+use crate::throttle::RateLimiter;
+#[cfg(feature = "metrics")]
+use crate::ReadStats;
+use crate::{
+    as_uninit_mut, assume_init_mut, check_pointer_size, check_read_size, is_aligned_pointer,
+    offset_address, CachePolicy, Endianness, MemoryReadError, MemoryRegion, Module,
+    OpenProcessError, Process, ProcessEntry, RegionKind, DEFAULT_MAX_READ_SIZE, PAGE_SIZE,
+};
+use libc::{
+    c_int, c_uint, c_void, dlopen, dlsym, kern_return_t, kill, mach_msg_type_number_t, mach_port_t,
+    mach_vm_address_t, mach_vm_size_t, pid_t, proc_listallpids, proc_name, proc_pidpath,
+    proc_regionfilename, sysconf, task_for_pid, vm_map_t, vm_prot_t, _SC_PAGESIZE, ESRCH,
+    KERN_INVALID_ADDRESS, KERN_SUCCESS, PROC_PIDPATHINFO_MAXSIZE, RTLD_LAZY, VM_PROT_EXECUTE,
+    VM_PROT_READ, VM_PROT_WRITE,
+};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::hash::{Hash, Hasher};
+use std::io::Error as IoError;
+use std::mem::{size_of, MaybeUninit};
+use std::os::unix::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const VM_REGION_BASIC_INFO_64: c_int = 9;
+
+/// `vm_region_basic_info_data_64_t`, which the headers declare with 4-byte packing.
+#[repr(C, packed(4))]
+#[derive(Default)]
+struct RegionBasicInfo {
+    protection: vm_prot_t,
+    max_protection: vm_prot_t,
+    inheritance: c_uint,
+    shared: c_uint,
+    reserved: c_uint,
+    offset: u64,
+    behavior: c_int,
+    user_wired_count: u16,
+}
+
+extern "C" {
+    fn mach_vm_read_overwrite(
+        target_task: vm_map_t,
+        address: mach_vm_address_t,
+        size: mach_vm_size_t,
+        data: mach_vm_address_t,
+        out_size: *mut mach_vm_size_t,
+    ) -> kern_return_t;
+
+    fn mach_vm_region(
+        target_task: vm_map_t,
+        address: *mut mach_vm_address_t,
+        size: *mut mach_vm_size_t,
+        flavor: c_int,
+        info: *mut c_int,
+        info_count: *mut mach_msg_type_number_t,
+        object_name: *mut mach_port_t,
+    ) -> kern_return_t;
+
+    fn mach_port_deallocate(task: mach_port_t, name: mach_port_t) -> kern_return_t;
+
+    static mach_task_self_: mach_port_t;
+}
+
+/// Task port of this process.
+fn mach_task_self() -> mach_port_t {
+    unsafe { mach_task_self_ }
+}
+
+/// Opens process with specified id.
+///
+/// If the process is not found or could not be opened `None` will be returned.
+pub fn open_process(pid: u32) -> Option<MacosProcess> {
+    open_process_checked(pid).ok()
+}
+
+/// Opens process with specified id, telling why it could not be opened.
+///
+/// `task_for_pid` is only allowed for root or with the `com.apple.security.cs.debugger`
+/// entitlement, and never for processes protected by System Integrity Protection.
+pub fn open_process_checked(pid: u32) -> Result<MacosProcess, OpenProcessError> {
+    let mut task: mach_port_t = 0;
+    let result = unsafe { task_for_pid(mach_task_self(), pid as pid_t, &mut task) };
+
+    if result != KERN_SUCCESS {
+        trace_event!(debug, pid, result, "failed to open process");
+
+        // `task_for_pid` fails the same way for missing and denied processes.
+        let exists = unsafe { kill(pid as pid_t, 0) } == 0
+            || IoError::last_os_error().raw_os_error() != Some(ESRCH);
+
+        if !exists {
+            return Err(OpenProcessError::NotFound { pid });
+        }
+
+        return Err(OpenProcessError::AccessDenied { pid });
+    }
+
+    trace_event!(debug, pid, "opened process");
+
+    Ok(MacosProcess {
+        pid,
+        task,
+        max_read_size: DEFAULT_MAX_READ_SIZE,
+        endianness: Endianness::default(),
+        regions: RegionCache::default(),
+        rate_limiter: None,
+        pointer_size: size_of::<usize>(),
+        #[cfg(feature = "metrics")]
+        read_counters: ReadCounters::default(),
+    })
+}
+
+/// Lists ids of all running processes.
+pub fn all_pids() -> Result<Vec<u32>, IoError> {
+    let count = unsafe { proc_listallpids(std::ptr::null_mut(), 0) };
+
+    if count < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    // Leave room for processes started between the two calls.
+    let mut pids = vec![0 as pid_t; count as usize + 64];
+    let size = (pids.len() * size_of::<pid_t>()) as c_int;
+    let count = unsafe { proc_listallpids(pids.as_mut_ptr() as *mut c_void, size) };
+
+    if count < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    pids.truncate(count as usize);
+
+    Ok(pids.into_iter().map(|pid| pid as u32).collect())
+}
+
+/// Lists running processes named after their executable, or the short process name when its
+/// path can't be resolved.
+pub(crate) fn process_entries() -> Result<Vec<ProcessEntry>, IoError> {
+    Ok(all_pids()?
+        .into_iter()
+        .filter_map(|pid| {
+            let name = match executable_path(pid) {
+                Some(path) => path.file_name()?.to_string_lossy().into_owned(),
+                None => short_name(pid)?,
+            };
+
+            Some(ProcessEntry { pid, name })
+        })
+        .collect())
+}
+
+/// Finds all processes with matching name.
+///
+/// The name is the file name of the executable. Processes that can't be opened are skipped.
+pub fn find_by_name(name: &str) -> Result<Vec<MacosProcess>, IoError> {
+    Ok(process_entries()?
+        .into_iter()
+        .filter(|entry| entry.name == name)
+        .filter_map(|entry| open_process(entry.pid))
+        .collect())
+}
+
+/// Full path of the executable of process `pid`.
+fn executable_path(pid: u32) -> Option<PathBuf> {
+    let mut buffer = vec![0u8; PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe {
+        proc_pidpath(
+            pid as c_int,
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len() as u32,
+        )
+    };
+
+    if len <= 0 {
+        return None;
+    }
+
+    buffer.truncate(len as usize);
+
+    Some(PathBuf::from(OsString::from_vec(buffer)))
+}
+
+/// Name the kernel keeps for process `pid`, truncated to 32 bytes.
+fn short_name(pid: u32) -> Option<String> {
+    let mut buffer = [0u8; 64];
+    let len = unsafe {
+        proc_name(
+            pid as c_int,
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len() as u32,
+        )
+    };
+
+    if len <= 0 {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&buffer[..len as usize]).into_owned())
+}
+
+/// Address of libobjc global `symbol`, a null-terminated name.
+///
+/// libobjc lies in the shared cache, which is mapped at the same address in every process of a
+/// boot, so the address looked up in this process is valid in the target as well.
+fn objc_symbol(symbol: &[u8]) -> Option<usize> {
+    static LIBOBJC: OnceLock<usize> = OnceLock::new();
+
+    let libobjc = *LIBOBJC.get_or_init(|| unsafe {
+        dlopen(
+            b"/usr/lib/libobjc.A.dylib\0".as_ptr() as *const _,
+            RTLD_LAZY,
+        ) as usize
+    });
+
+    if libobjc == 0 {
+        return None;
+    }
+
+    match unsafe { dlsym(libobjc as *mut c_void, symbol.as_ptr() as *const _) } as usize {
+        0 => None,
+        address => Some(address),
+    }
+}
+
+#[derive(Debug)]
+pub struct MacosProcess {
+    pid: u32,
+    /// Task port `task_for_pid` returned.
+    task: mach_port_t,
+    max_read_size: usize,
+    endianness: Endianness,
+    regions: RegionCache,
+    rate_limiter: Option<RateLimiter>,
+    pointer_size: usize,
+    #[cfg(feature = "metrics")]
+    read_counters: ReadCounters,
+}
+
+impl PartialEq for MacosProcess {
+    fn eq(&self, other: &Self) -> bool {
+        self.pid == other.pid
+    }
+}
+
+impl Eq for MacosProcess {}
+
+impl Hash for MacosProcess {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pid.hash(state);
+    }
+}
+
+impl MacosProcess {
+    /// Id of the process.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// File name of the executable.
+    pub fn name(&self) -> Option<String> {
+        Some(self.exe_path()?.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Full path of the executable.
+    pub fn exe_path(&self) -> Option<PathBuf> {
+        executable_path(self.pid)
+    }
+
+    /// Sets the largest amount of bytes a single `read_bytes` call may request.
+    pub fn set_max_read_size(&mut self, max_read_size: usize) {
+        self.max_read_size = max_read_size;
+    }
+
+    /// Limits reads to at most `reads_per_second`, sleeping before a read when needed.
+    ///
+    /// `None` or 0 removes the limit.
+    pub fn set_rate_limit(&mut self, reads_per_second: Option<u32>) {
+        self.rate_limiter = reads_per_second.and_then(RateLimiter::new);
+    }
+
+    /// Reads per second the process is limited to, if any.
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limiter
+            .as_ref()
+            .map(RateLimiter::reads_per_second)
+    }
+
+    /// Sets how long the region list `is_plausible_pointer` checks against is cached.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.regions.set_policy(policy);
+    }
+
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.regions.policy()
+    }
+
+    /// Reloads the region list `is_plausible_pointer` checks against.
+    pub fn refresh_regions(&self) -> Result<(), MemoryReadError> {
+        self.regions.refresh(|| self.memory_regions())
+    }
+
+    /// Counters of the reads made so far.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> ReadStats {
+        self.read_counters.stats()
+    }
+
+    /// Zeroes the read counters.
+    #[cfg(feature = "metrics")]
+    pub fn reset_stats(&self) {
+        self.read_counters.reset();
+    }
+
+    /// Sets the byte order the number readers decode with.
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Overrides the size of the pointers `read_ptr` and the pointer chains read, which is the
+    /// size of this process' pointers by default.
+    ///
+    /// Fails with `InvalidFormat` unless the size is 4 or 8.
+    pub fn set_pointer_size(&mut self, pointer_size: usize) -> Result<(), MemoryReadError> {
+        check_pointer_size(pointer_size)?;
+        self.pointer_size = pointer_size;
+
+        Ok(())
+    }
+
+    /// Reads the characters of the `NSString` object at the address, which is either a tagged
+    /// pointer or a CoreFoundation string such as `__NSCFString` and `__NSCFConstantString`.
+    ///
+    /// Tagged pointers are decoded with the obfuscation secrets of the target's libobjc, so the
+    /// target must run the architecture of this process. Other subclasses, such as bridged Swift
+    /// strings, fail with `InvalidFormat`.
+    pub fn read_nsstring(&self, address: usize) -> Result<String, MemoryReadError> {
+        let tagged_pointers = if cfg!(target_arch = "x86_64") {
+            TaggedPointers::LowBit
+        } else {
+            TaggedPointers::Split
+        };
+
+        if !tagged_pointers.is_tagged(address as u64) {
+            return nsstring::read_cfstring(self, address);
+        }
+
+        let obfuscator =
+            objc_symbol(b"objc_debug_taggedpointer_obfuscator\0").ok_or_else(|| {
+                MemoryReadError::ExportNotFound {
+                    name: "objc_debug_taggedpointer_obfuscator".to_string(),
+                }
+            })?;
+        let obfuscator = self.read_u64(obfuscator)?;
+        // Runtimes that don't permute the class index lack the table.
+        let mut permutations = [0, 1, 2, 3, 4, 5, 6, 7];
+
+        if let Some(table) = objc_symbol(b"objc_debug_tag60_permutations\0") {
+            self.read_bytes(table, &mut permutations)?;
+        }
+
+        match tagged_pointers.nsstring_payload(address as u64, obfuscator, &permutations) {
+            Some(payload) => nsstring::decode_tagged(payload),
+            None => Err(MemoryReadError::InvalidFormat {
+                reason: "tagged pointer is not an NSString",
+            }),
+        }
+    }
+
+    /// Path of the file mapped at the address, if any.
+    fn region_file_name(&self, address: usize) -> Option<PathBuf> {
+        let mut buffer = vec![0u8; PROC_PIDPATHINFO_MAXSIZE as usize];
+        let len = unsafe {
+            proc_regionfilename(
+                self.pid as c_int,
+                address as u64,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len() as u32,
+            )
+        };
+
+        if len <= 0 {
+            return None;
+        }
+
+        buffer.truncate(len as usize);
+
+        Some(PathBuf::from(OsString::from_vec(buffer)))
+    }
+
+    fn read_task_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        // Empty reads are answered without asking the OS, which handles them inconsistently.
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait();
+        }
+
+        let result = self.read_memory(address, buffer);
+
+        #[cfg(feature = "metrics")]
+        self.read_counters.record(&result);
+
+        result
+    }
+
+    /// Reads as much of `buffer` as is readable from the address on.
+    ///
+    /// `mach_vm_read_overwrite` fails as a whole when the range crosses an unmapped page, so
+    /// such reads are retried page by page up to the first inaccessible one.
+    fn read_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        check_read_size(buffer.len(), self.max_read_size)?;
+        offset_address(address, buffer.len())?;
+
+        if let Some(read) = self.read_task(address, buffer) {
+            return Ok(read);
+        }
+
+        let page_size = self.page_size();
+        let mut read = 0;
+
+        while read < buffer.len() {
+            let chunk_address = address.wrapping_add(read);
+            let len = (page_size - chunk_address % page_size).min(buffer.len() - read);
+
+            match self.read_task(chunk_address, &mut buffer[read..read + len]) {
+                Some(chunk_read) if chunk_read == len => read += len,
+                Some(chunk_read) => return Ok(read + chunk_read),
+                None => break,
+            }
+        }
+
+        if read == 0 {
+            return Err(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: None,
+            });
+        }
+
+        Ok(read)
+    }
+
+    /// Reads with a single `mach_vm_read_overwrite` call.
+    fn read_task(&self, address: usize, buffer: &mut [MaybeUninit<u8>]) -> Option<usize> {
+        let mut read: mach_vm_size_t = 0;
+
+        let result = unsafe {
+            mach_vm_read_overwrite(
+                self.task,
+                address as mach_vm_address_t,
+                buffer.len() as mach_vm_size_t,
+                buffer.as_mut_ptr() as mach_vm_address_t,
+                &mut read,
+            )
+        };
+
+        (result == KERN_SUCCESS).then_some(read as usize)
+    }
+}
+
+impl Process for MacosProcess {
+    /// Finds the base of the module loaded from a file with matching name.
+    ///
+    /// System libraries loaded from the dyld shared cache are mapped as part of the cache
+    /// file, so they can't be found by their own names.
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        let base = self
+            .modules()
+            .ok()?
+            .into_iter()
+            .find(|module| module.name == module_name)
+            .map(|module| module.base);
+        trace_event!(debug, pid = self.pid, module_name, base = ?base, "resolved module base address");
+
+        base
+    }
+
+    fn main_module_base(&self) -> Option<usize> {
+        let exe = self.exe_path()?;
+
+        self.modules()
+            .ok()?
+            .into_iter()
+            .find(|module| module.path == exe)
+            .map(|module| module.base)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        let mut regions = Vec::new();
+        let mut address: mach_vm_address_t = 0;
+
+        loop {
+            let mut size: mach_vm_size_t = 0;
+            let mut info = RegionBasicInfo::default();
+            let mut count = (size_of::<RegionBasicInfo>() / size_of::<c_int>()) as u32;
+            let mut object_name: mach_port_t = 0;
+
+            let result = unsafe {
+                mach_vm_region(
+                    self.task,
+                    &mut address,
+                    &mut size,
+                    VM_REGION_BASIC_INFO_64,
+                    &mut info as *mut RegionBasicInfo as *mut c_int,
+                    &mut count,
+                    &mut object_name,
+                )
+            };
+
+            // Querying past the highest mapped address ends the walk.
+            if result == KERN_INVALID_ADDRESS {
+                break;
+            }
+
+            if result != KERN_SUCCESS {
+                return Err(MemoryReadError::InaccessibleMemoryAddress {
+                    address: address as usize,
+                    os_error: None,
+                });
+            }
+
+            let base = address as usize;
+            let protection = info.protection;
+
+            regions.push(MemoryRegion {
+                base,
+                size: size as usize,
+                readable: protection & VM_PROT_READ != 0,
+                writable: protection & VM_PROT_WRITE != 0,
+                executable: protection & VM_PROT_EXECUTE != 0,
+                kind: self
+                    .region_file_name(base)
+                    .map_or(RegionKind::Anonymous, RegionKind::File),
+            });
+
+            address = match address.checked_add(size) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        Ok(regions)
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        let mut modules: Vec<Module> = Vec::new();
+        let mut indices: HashMap<PathBuf, usize> = HashMap::new();
+
+        for region in self.memory_regions()? {
+            let path = match &region.kind {
+                RegionKind::File(path) => path,
+                _ => continue,
+            };
+
+            match indices.get(path) {
+                Some(&index) => {
+                    let module = &mut modules[index];
+                    let end = module.end().max(region.end());
+                    module.base = module.base.min(region.base);
+                    module.size = end - module.base;
+                }
+                None => {
+                    indices.insert(path.clone(), modules.len());
+
+                    let name = path.file_name().unwrap_or_default();
+
+                    modules.push(Module {
+                        name: name.to_string_lossy().into_owned(),
+                        path: path.clone(),
+                        base: region.base,
+                        size: region.size,
+                    });
+                }
+            }
+        }
+
+        modules.sort_by_key(|module| module.base);
+
+        Ok(modules)
+    }
+
+    fn is_plausible_pointer(&self, value: usize) -> bool {
+        is_aligned_pointer(self, value) && self.regions.is_readable(value, || self.memory_regions())
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn max_read_size(&self) -> usize {
+        self.max_read_size
+    }
+
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(self.pointer_size)
+    }
+
+    fn page_size(&self) -> usize {
+        match unsafe { sysconf(_SC_PAGESIZE) } {
+            size if size > 0 => size as usize,
+            _ => PAGE_SIZE,
+        }
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        let result = self
+            .read_task_memory(address, as_uninit_mut(buffer))
+            .and_then(|read| {
+                if read != buffer.len() {
+                    return Err(MemoryReadError::LessBytesRead {
+                        expected: buffer.len(),
+                        actual: read,
+                    });
+                }
+
+                Ok(())
+            });
+        trace_event!(
+            trace,
+            pid = self.pid,
+            address,
+            len = buffer.len(),
+            error = ?result.as_ref().err(),
+            "read_bytes"
+        );
+
+        result
+    }
+
+    fn read_bytes_uninit<'a>(
+        &self,
+        address: usize,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], MemoryReadError> {
+        let read = self.read_task_memory(address, buffer)?;
+
+        if read != buffer.len() {
+            return Err(MemoryReadError::LessBytesRead {
+                expected: buffer.len(),
+                actual: read,
+            });
+        }
+
+        Ok(unsafe { assume_init_mut(buffer) })
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        self.read_task_memory(address, as_uninit_mut(buffer))
+    }
+}
+
+impl Drop for MacosProcess {
+    fn drop(&mut self) {
+        unsafe {
+            mach_port_deallocate(mach_task_self(), self.task);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use crate::core_dump::{read_at, Segment};
+use crate::{MemoryReadError, MemoryRegion, Module, RegionKind};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Signature starting every minidump (`"MDMP"` in ASCII).
+pub(crate) const MINIDUMP_SIGNATURE: &[u8; 4] = b"MDMP";
+const HEADER_SIZE: usize = 32;
+const DIRECTORY_ENTRY_SIZE: usize = 12;
+const MODULE_LIST_STREAM: u32 = 4;
+const MEMORY_LIST_STREAM: u32 = 5;
+const SYSTEM_INFO_STREAM: u32 = 7;
+const MEMORY64_LIST_STREAM: u32 = 9;
+const MEMORY_INFO_LIST_STREAM: u32 = 16;
+const MINIDUMP_MODULE_SIZE: usize = 108;
+const MEMORY_DESCRIPTOR_SIZE: usize = 16;
+const PROCESSOR_ARCHITECTURE_INTEL: u16 = 0;
+const PROCESSOR_ARCHITECTURE_ARM: u16 = 5;
+const MEM_IMAGE: u32 = 0x100_0000;
+const PAGE_NOACCESS: u32 = 0x01;
+const PAGE_WRITABLE: u32 = 0x04 | 0x08 | 0x40 | 0x80;
+const PAGE_EXECUTABLE: u32 = 0x10 | 0x20 | 0x40 | 0x80;
+const PAGE_GUARD: u32 = 0x100;
+
+/// Memory ranges and modules stored in a Windows minidump.
+pub(crate) struct Minidump {
+    pub(crate) segments: Vec<Segment>,
+    pub(crate) modules: Vec<Module>,
+    /// Base of the executable, which the module list starts with.
+    pub(crate) main_module_base: Option<usize>,
+    pub(crate) pointer_size: usize,
+}
+
+fn truncated() -> MemoryReadError {
+    MemoryReadError::InvalidFormat {
+        reason: "truncated minidump",
+    }
+}
+
+fn field<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], MemoryReadError> {
+    let end = offset.checked_add(N).ok_or_else(truncated)?;
+    let mut field = [0u8; N];
+    field.copy_from_slice(bytes.get(offset..end).ok_or_else(truncated)?);
+
+    Ok(field)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, MemoryReadError> {
+    Ok(u16::from_le_bytes(field(bytes, offset)?))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, MemoryReadError> {
+    Ok(u32::from_le_bytes(field(bytes, offset)?))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> Result<u64, MemoryReadError> {
+    Ok(u64::from_le_bytes(field(bytes, offset)?))
+}
+
+/// `u64` address or size, which does not fit `usize` of 32-bit hosts reading 64-bit dumps.
+fn usize_at(bytes: &[u8], offset: usize) -> Result<usize, MemoryReadError> {
+    usize::try_from(u64_at(bytes, offset)?).map_err(|_| truncated())
+}
+
+/// Stream directory of the minidump, read from the file on demand.
+struct Streams<'a> {
+    file: &'a mut File,
+    file_len: u64,
+    directory: Vec<u8>,
+}
+
+impl Streams<'_> {
+    /// Reads the first stream of the type, if the dump has one.
+    fn read(&mut self, kind: u32) -> Result<Option<Vec<u8>>, MemoryReadError> {
+        for entry in self.directory.chunks_exact(DIRECTORY_ENTRY_SIZE) {
+            if u32_at(entry, 0)? == kind {
+                let size = u32_at(entry, 4)? as usize;
+                let rva = u32_at(entry, 8)? as u64;
+
+                return read_at(self.file, self.file_len, rva, size, truncated).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Reads the memory and module lists of the minidump.
+///
+/// Full memory dumps store their ranges in `Memory64ListStream`, smaller ones in
+/// `MemoryListStream`. Protections come from `MemoryInfoListStream` when the dump has it.
+pub(crate) fn parse(file: &mut File, file_len: u64) -> Result<Minidump, MemoryReadError> {
+    let header = read_at(file, file_len, 0, HEADER_SIZE, truncated)?;
+    let stream_count = u32_at(&header, 8)? as usize;
+    let directory_rva = u32_at(&header, 12)? as u64;
+    let directory = read_at(
+        file,
+        file_len,
+        directory_rva,
+        stream_count
+            .checked_mul(DIRECTORY_ENTRY_SIZE)
+            .ok_or_else(truncated)?,
+        truncated,
+    )?;
+
+    let mut streams = Streams {
+        file,
+        file_len,
+        directory,
+    };
+
+    let pointer_size = match streams.read(SYSTEM_INFO_STREAM)? {
+        Some(info) => match u16_at(&info, 0)? {
+            PROCESSOR_ARCHITECTURE_INTEL | PROCESSOR_ARCHITECTURE_ARM => 4,
+            _ => 8,
+        },
+        None => 8,
+    };
+
+    let mut modules = match streams.read(MODULE_LIST_STREAM)? {
+        Some(list) => read_modules(&mut streams, &list)?,
+        None => Vec::new(),
+    };
+
+    let memory_info = match streams.read(MEMORY_INFO_LIST_STREAM)? {
+        Some(list) => read_memory_info(&list)?,
+        None => Vec::new(),
+    };
+
+    let ranges = match streams.read(MEMORY64_LIST_STREAM)? {
+        Some(list) => read_memory64_list(&list)?,
+        None => match streams.read(MEMORY_LIST_STREAM)? {
+            Some(list) => read_memory_list(&list)?,
+            None => Vec::new(),
+        },
+    };
+
+    let main_module_base = modules.first().map(|module| module.base);
+    let mut segments = Vec::with_capacity(ranges.len());
+
+    for (base, file_offset, size) in ranges {
+        match file_offset.checked_add(size as u64) {
+            Some(end) if end <= file_len => {}
+            _ => return Err(truncated()),
+        }
+
+        let info = memory_info
+            .iter()
+            .find(|info| (info.base..info.base.saturating_add(info.size)).contains(&base));
+        let protection = info.map_or(0, |info| info.protection);
+        let accessible = protection & (PAGE_GUARD | PAGE_NOACCESS) == 0;
+        let module = modules.iter().find(|module| module.contains(base));
+
+        segments.push(Segment {
+            region: MemoryRegion {
+                base,
+                size,
+                // Bytes stored in the dump can always be read back.
+                readable: true,
+                writable: accessible && protection & PAGE_WRITABLE != 0,
+                executable: accessible && protection & PAGE_EXECUTABLE != 0,
+                kind: match module {
+                    Some(module) if info.is_none_or(|info| info.kind == MEM_IMAGE) => {
+                        RegionKind::File(module.path.clone())
+                    }
+                    _ => RegionKind::Anonymous,
+                },
+            },
+            file_offset,
+            file_size: size,
+        });
+    }
+
+    segments.sort_by_key(|segment| segment.region.base);
+    modules.sort_by_key(|module| module.base);
+
+    Ok(Minidump {
+        segments,
+        modules,
+        main_module_base,
+        pointer_size,
+    })
+}
+
+/// Reads `MINIDUMP_MODULE` entries and their `MINIDUMP_STRING` names.
+fn read_modules(streams: &mut Streams, list: &[u8]) -> Result<Vec<Module>, MemoryReadError> {
+    let count = u32_at(list, 0)? as usize;
+    let mut modules = Vec::with_capacity(count.min(list.len() / MINIDUMP_MODULE_SIZE));
+
+    for index in 0..count {
+        let entry = 4 + index * MINIDUMP_MODULE_SIZE;
+        let name_rva = u32_at(list, entry + 20)? as u64;
+        let name_len = u32_at(
+            &read_at(streams.file, streams.file_len, name_rva, 4, truncated)?,
+            0,
+        )?;
+        let name = read_at(
+            streams.file,
+            streams.file_len,
+            name_rva + 4,
+            name_len as usize,
+            truncated,
+        )?;
+
+        let units: Vec<u16> = name
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        let path = String::from_utf16_lossy(&units);
+
+        modules.push(Module {
+            // Paths are Windows ones, which `Path` of other hosts does not split.
+            name: path
+                .rsplit(['\\', '/'])
+                .next()
+                .unwrap_or_default()
+                .to_owned(),
+            path: PathBuf::from(path),
+            base: usize_at(list, entry)?,
+            size: u32_at(list, entry + 8)? as usize,
+        });
+    }
+
+    Ok(modules)
+}
+
+/// Reads `(base, file offset, size)` of the `MINIDUMP_MEMORY_DESCRIPTOR` entries.
+fn read_memory_list(list: &[u8]) -> Result<Vec<(usize, u64, usize)>, MemoryReadError> {
+    let count = u32_at(list, 0)? as usize;
+
+    (0..count)
+        .map(|index| {
+            let entry = 4 + index * MEMORY_DESCRIPTOR_SIZE;
+
+            Ok((
+                usize_at(list, entry)?,
+                u32_at(list, entry + 12)? as u64,
+                u32_at(list, entry + 8)? as usize,
+            ))
+        })
+        .collect()
+}
+
+/// Reads `(base, file offset, size)` of the `MINIDUMP_MEMORY_DESCRIPTOR64` entries, whose
+/// bytes are stored back to back from `BaseRva`.
+fn read_memory64_list(list: &[u8]) -> Result<Vec<(usize, u64, usize)>, MemoryReadError> {
+    let count = usize_at(list, 0)?;
+    let mut file_offset = u64_at(list, 8)?;
+    let mut ranges = Vec::with_capacity(count.min(list.len() / MEMORY_DESCRIPTOR_SIZE));
+
+    for index in 0..count {
+        let entry = 16 + index * MEMORY_DESCRIPTOR_SIZE;
+        let size = usize_at(list, entry + 8)?;
+
+        ranges.push((usize_at(list, entry)?, file_offset, size));
+        file_offset = file_offset.checked_add(size as u64).ok_or_else(truncated)?;
+    }
+
+    Ok(ranges)
+}
+
+/// Region of the address space as `MINIDUMP_MEMORY_INFO` describes it.
+struct MemoryInfo {
+    base: usize,
+    size: usize,
+    protection: u32,
+    kind: u32,
+}
+
+fn read_memory_info(list: &[u8]) -> Result<Vec<MemoryInfo>, MemoryReadError> {
+    let header_size = u32_at(list, 0)? as usize;
+    let entry_size = u32_at(list, 4)? as usize;
+    let count = usize_at(list, 8)?;
+
+    // Fields up to `Type` are read from every entry.
+    if entry_size < 44 {
+        return Err(truncated());
+    }
+
+    (0..count)
+        .map(|index| {
+            let entry = index
+                .checked_mul(entry_size)
+                .and_then(|offset| offset.checked_add(header_size))
+                .ok_or_else(truncated)?;
+
+            Ok(MemoryInfo {
+                base: usize_at(list, entry)?,
+                size: usize_at(list, entry + 24)?,
+                protection: u32_at(list, entry + 36)?,
+                kind: u32_at(list, entry + 40)?,
+            })
+        })
+        .collect()
+}
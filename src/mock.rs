@@ -0,0 +1,183 @@
+use crate::region::find_region;
+use crate::{
+    check_read_size, Endianness, MemoryReadError, MemoryRegion, Module, Process, RegionKind,
+};
+use std::mem::size_of;
+use std::path::PathBuf;
+
+/// Process whose memory is made of byte buffers placed at fixed addresses.
+///
+/// Every reader built on `Process` works against it without a live target, so code using the
+/// crate can be tested deterministically.
+///
+/// ```
+/// use process_memory_reader::{MockProcess, Process};
+///
+/// let process = MockProcess::new().with_region(0x1000, vec![0x78, 0x56, 0x34, 0x12]);
+///
+/// assert_eq!(process.read_u32(0x1000).unwrap(), 0x1234_5678);
+/// assert!(process.read_u32(0x1002).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockProcess {
+    /// Regions sorted by base address, with the bytes of each at the same index of `bytes`.
+    regions: Vec<MemoryRegion>,
+    bytes: Vec<Vec<u8>>,
+    modules: Vec<Module>,
+    endianness: Endianness,
+    pointer_size: usize,
+}
+
+impl Default for MockProcess {
+    fn default() -> Self {
+        MockProcess {
+            regions: Vec::new(),
+            bytes: Vec::new(),
+            modules: Vec::new(),
+            endianness: Endianness::default(),
+            pointer_size: size_of::<usize>(),
+        }
+    }
+}
+
+impl MockProcess {
+    /// Creates process without memory, with the host byte order and pointer size.
+    pub fn new() -> Self {
+        MockProcess::default()
+    }
+
+    /// Maps `bytes` as a readable and writable anonymous region at `base`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is empty or overlaps one mapped before.
+    pub fn with_region(mut self, base: usize, bytes: Vec<u8>) -> Self {
+        let region = MemoryRegion {
+            base,
+            size: bytes.len(),
+            readable: true,
+            writable: true,
+            executable: false,
+            kind: RegionKind::Anonymous,
+        };
+
+        assert!(!bytes.is_empty(), "region must not be empty");
+        assert!(
+            self.regions
+                .iter()
+                .all(|other| region.end() <= other.base || other.end() <= region.base),
+            "region must not overlap mapped regions"
+        );
+
+        let index = self.regions.partition_point(|other| other.base < base);
+        self.regions.insert(index, region);
+        self.bytes.insert(index, bytes);
+        self
+    }
+
+    /// Lists module `name` at `base`, which `base_address` and `modules` report.
+    pub fn with_module(mut self, name: &str, base: usize, size: usize) -> Self {
+        self.modules.push(Module {
+            name: name.to_owned(),
+            path: PathBuf::from(name),
+            base,
+            size,
+        });
+        self.modules.sort_by_key(|module| module.base);
+        self
+    }
+
+    /// Byte order the number readers decode with.
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Size of the pointers `read_ptr` and the pointer chains read.
+    pub fn with_pointer_size(mut self, pointer_size: usize) -> Self {
+        self.pointer_size = pointer_size;
+        self
+    }
+}
+
+impl Process for MockProcess {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        self.modules
+            .iter()
+            .find(|module| module.name == module_name)
+            .map(|module| module.base)
+    }
+
+    fn main_module_base(&self) -> Option<usize> {
+        self.modules.first().map(|module| module.base)
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        let read = self.read_bytes_partial(address, buffer)?;
+
+        if read == 0 && !buffer.is_empty() {
+            return Err(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: None,
+            });
+        }
+
+        if read != buffer.len() {
+            return Err(MemoryReadError::LessBytesRead {
+                expected: buffer.len(),
+                actual: read,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        check_read_size(buffer.len(), self.max_read_size())?;
+
+        let mut read = 0;
+
+        // Adjacent regions are read one after another.
+        while read < buffer.len() {
+            let current = match address.checked_add(read) {
+                Some(current) => current,
+                None => break,
+            };
+            let region = match find_region(&self.regions, current) {
+                Some(region) => region,
+                None => break,
+            };
+
+            let index = self
+                .regions
+                .partition_point(|other| other.base < region.base);
+            let offset = current - region.base;
+            let len = (region.size - offset).min(buffer.len() - read);
+
+            buffer[read..read + len].copy_from_slice(&self.bytes[index][offset..offset + len]);
+            read += len;
+        }
+
+        Ok(read)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        Ok(self.regions.clone())
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        Ok(self.modules.clone())
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(self.pointer_size)
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{elf, pe, MemoryReadError, Process};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Module (executable or shared library) loaded into the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub path: PathBuf,
+    pub base: usize,
+    pub size: usize,
+}
+
+impl Module {
+    /// First address past the end of the module image.
+    pub fn end(&self) -> usize {
+        self.base.saturating_add(self.size)
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.base && address < self.end()
+    }
+}
+
+/// Module with its position in the load order, created by `WindowsProcess::modules_detailed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedModule {
+    pub module: Module,
+    /// Index in the loader list, the main executable being 0.
+    pub load_order: usize,
+    /// `TimeDateStamp` of the PE file header, if the header could be read.
+    pub timestamp: Option<u32>,
+}
+
+/// Export table of a module, created by `Process::module_exports`.
+#[derive(Debug, Clone)]
+pub struct ModuleExports {
+    module: Module,
+    addresses: HashMap<String, usize>,
+}
+
+impl ModuleExports {
+    pub(crate) fn new(module: Module, exports: Vec<(String, usize)>) -> Self {
+        ModuleExports {
+            module,
+            addresses: exports.into_iter().collect(),
+        }
+    }
+
+    /// Module the exports belong to.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Absolute address of the export.
+    pub fn address_of(&self, name: &str) -> Result<usize, MemoryReadError> {
+        self.addresses
+            .get(name)
+            .copied()
+            .ok_or_else(|| MemoryReadError::ExportNotFound {
+                name: name.to_owned(),
+            })
+    }
+
+    /// Iterates over `(name, address)` of every export in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.addresses
+            .iter()
+            .map(|(name, &address)| (name.as_str(), address))
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+}
+
+/// Pointer value annotated with the module it points into, created by `Process::read_pointer`.
+///
+/// Displays as `0x7ff6a0001234 (game.exe+0x1234)`, or just the value outside of modules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPointer {
+    pub value: usize,
+    /// Whether the value is non-null and points into readable memory.
+    pub plausible: bool,
+    /// Name of the module containing the value and the offset from its base.
+    pub module: Option<(String, usize)>,
+}
+
+impl ResolvedPointer {
+    pub fn is_null(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl fmt::Display for ResolvedPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.value)?;
+
+        if let Some((name, offset)) = &self.module {
+            write!(f, " ({}+{:#x})", name, offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads exports of the module, detecting its image format from the mapped header.
+pub(crate) fn read_exports<P: Process + ?Sized>(
+    process: &P,
+    module: &Module,
+) -> Result<Vec<(String, usize)>, MemoryReadError> {
+    let mut magic = [0u8; 4];
+    process.read_bytes(module.base, &mut magic)?;
+
+    match magic {
+        [b'M', b'Z', _, _] => pe::exports(process, module.base),
+        [0x7f, b'E', b'L', b'F'] => elf::exports(&module.path, module.base),
+        _ => Err(MemoryReadError::InvalidFormat {
+            reason: "unknown module image format",
+        }),
+    }
+}
+
+/// Reads imports of the module, which only PE images carry in process memory.
+pub(crate) fn read_imports<P: Process + ?Sized>(
+    process: &P,
+    module: &Module,
+) -> Result<Vec<(String, String, usize)>, MemoryReadError> {
+    let mut magic = [0u8; 2];
+    process.read_bytes(module.base, &mut magic)?;
+
+    match magic {
+        [b'M', b'Z'] => pe::imports(process, module.base),
+        _ => Err(MemoryReadError::InvalidFormat {
+            reason: "module is not a PE image",
+        }),
+    }
+}
+
+/// Reads the base address the module's file asks to be loaded at.
+pub(crate) fn preferred_base<P: Process + ?Sized>(
+    process: &P,
+    module: &Module,
+) -> Result<usize, MemoryReadError> {
+    let mut magic = [0u8; 4];
+    process.read_bytes(module.base, &mut magic)?;
+
+    // The loader may update the mapped headers, so the file on disk is parsed.
+    match magic {
+        [b'M', b'Z', _, _] => pe::preferred_base(&module.path),
+        [0x7f, b'E', b'L', b'F'] => elf::preferred_base(&module.path),
+        _ => Err(MemoryReadError::InvalidFormat {
+            reason: "unknown module image format",
+        }),
+    }
+}
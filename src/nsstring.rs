@@ -0,0 +1,289 @@
+use crate::{decode_utf16, offset_address, MemoryReadError, Process};
+
+/// Index of `NSString` among the basic tagged pointer classes.
+const NSSTRING_TAG: u64 = 2;
+/// Type id CoreFoundation registers `CFString` with.
+const CFSTRING_TYPE_ID: u64 = 7;
+/// Characters of the 6 and 5-bit packed tagged strings, by their packed value.
+const PACKED_CHARACTERS: &[u8; 64] =
+    b"eilotrm.apdnsIc ufkMShjTRxgC4013bDNvwyUL2O856P-B79AFKEWV_zGJ/HYX";
+const IS_MUTABLE: u8 = 0x01;
+const HAS_LENGTH_BYTE: u8 = 0x04;
+const IS_UNICODE: u8 = 0x10;
+/// Info bits that are 0 when the characters follow the `CFString` header.
+const CONTENTS_MASK: u8 = 0x60;
+
+/// How the Objective-C runtime of the architecture lays out tagged pointers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TaggedPointers {
+    /// x86_64, marking tagged pointers with the low bit, followed by the class index.
+    LowBit,
+    /// arm64, marking tagged pointers with the high bit and permuting the class index kept in
+    /// the low bits.
+    Split,
+}
+
+impl TaggedPointers {
+    pub(crate) fn is_tagged(self, value: u64) -> bool {
+        match self {
+            TaggedPointers::LowBit => value & 1 != 0,
+            TaggedPointers::Split => value >> 63 != 0,
+        }
+    }
+
+    /// Payload of tagged pointer `value` if it is a tagged `NSString`.
+    ///
+    /// `obfuscator` and `permutations` are `objc_debug_taggedpointer_obfuscator` and
+    /// `objc_debug_tag60_permutations` of the target, which are random for every process.
+    pub(crate) fn nsstring_payload(
+        self,
+        value: u64,
+        obfuscator: u64,
+        permutations: &[u8; 8],
+    ) -> Option<u64> {
+        let value = value ^ obfuscator;
+        let (tag, payload) = match self {
+            TaggedPointers::LowBit => ((value >> 1) & 7, value >> 4),
+            TaggedPointers::Split => {
+                let tag = permutations[..7]
+                    .iter()
+                    .position(|&tag| u64::from(tag) == value & 7)
+                    .map_or(7, |tag| tag as u64);
+
+                (tag, (value << 1) >> 4)
+            }
+        };
+
+        (tag == NSSTRING_TAG).then_some(payload)
+    }
+}
+
+/// Decodes the characters of a tagged `NSString` payload.
+///
+/// The low 4 bits hold the length. Up to 7 characters are stored as bytes, up to 9 as 6-bit
+/// and up to 11 as 5-bit indexes into the table of frequent characters, the last one lowest.
+pub(crate) fn decode_tagged(payload: u64) -> Result<String, MemoryReadError> {
+    let length = (payload & 0xf) as usize;
+    let data = payload >> 4;
+
+    let bits = match length {
+        0..=7 => {
+            return Ok(data.to_le_bytes()[..length]
+                .iter()
+                .map(|&byte| char::from(byte))
+                .collect())
+        }
+        8..=9 => 6,
+        10..=11 => 5,
+        _ => return Err(MemoryReadError::InvalidLength { length, max: 11 }),
+    };
+
+    Ok((0..length)
+        .rev()
+        .map(|index| {
+            let packed = (data >> (bits * index)) & ((1 << bits) - 1);
+
+            char::from(PACKED_CHARACTERS[packed as usize])
+        })
+        .collect())
+}
+
+/// Reads the characters of the 64-bit `CFString` at the address, such as `__NSCFString` and
+/// `__NSCFConstantString` objects.
+///
+/// 8-bit strings are decoded as Latin-1, which covers the ASCII ones the compiler emits.
+pub(crate) fn read_cfstring<P: Process + ?Sized>(
+    process: &P,
+    address: usize,
+) -> Result<String, MemoryReadError> {
+    if process.pointer_size()? != 8 {
+        return Err(MemoryReadError::InvalidFormat {
+            reason: "32-bit CFString layouts are not supported",
+        });
+    }
+
+    // `CFRuntimeBase` is the isa followed by the info word, whose low byte holds the string
+    // flags and the next bits the type id.
+    let info = process.read_u64(offset_address(address, 8)?)?;
+
+    if (info >> 8) & 0x3ff != CFSTRING_TYPE_ID {
+        return Err(MemoryReadError::InvalidFormat {
+            reason: "object is not a CFString",
+        });
+    }
+
+    let flags = info as u8;
+    let fields = offset_address(address, 16)?;
+    // Only immutable strings with a length byte leave out the length field.
+    let has_length_field = flags & (IS_MUTABLE | HAS_LENGTH_BYTE) != HAS_LENGTH_BYTE;
+
+    let (contents, length) = if flags & CONTENTS_MASK == 0 {
+        if has_length_field {
+            (offset_address(fields, 8)?, Some(process.read_u64(fields)?))
+        } else {
+            (fields, None)
+        }
+    } else {
+        let length = if has_length_field {
+            Some(process.read_u64(offset_address(fields, 8)?)?)
+        } else {
+            None
+        };
+
+        (process.read_ptr(fields)?, length)
+    };
+
+    let length = match length {
+        Some(length) => length as usize,
+        None => process.read_u8(contents)? as usize,
+    };
+    let contents = if flags & HAS_LENGTH_BYTE != 0 {
+        offset_address(contents, 1)?
+    } else {
+        contents
+    };
+
+    if flags & IS_UNICODE != 0 {
+        let size = length
+            .checked_mul(2)
+            .ok_or(MemoryReadError::InvalidLength {
+                length,
+                max: usize::MAX / 2,
+            })?;
+
+        return Ok(decode_utf16(
+            &process.read_bytes_vec(contents, size)?,
+            process.endianness(),
+        ));
+    }
+
+    Ok(process
+        .read_bytes_vec(contents, length)?
+        .into_iter()
+        .map(char::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockProcess;
+
+    const BASE: usize = 0x1000;
+    const IDENTITY: [u8; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+
+    fn packed(string: &str, bits: usize) -> u64 {
+        string
+            .bytes()
+            .rev()
+            .enumerate()
+            .fold(0, |data, (index, ch)| {
+                let packed = PACKED_CHARACTERS.iter().position(|&c| c == ch).unwrap() as u64;
+
+                data | packed << (bits * index)
+            })
+    }
+
+    #[test]
+    fn decodes_tagged_strings() {
+        let bytes = u64::from_le_bytes(*b"abc\0\0\0\0\0");
+
+        assert_eq!(decode_tagged(bytes << 4 | 3).unwrap(), "abc");
+        assert_eq!(decode_tagged(0).unwrap(), "");
+        assert_eq!(
+            decode_tagged(packed("eilotrm.a", 6) << 4 | 9).unwrap(),
+            "eilotrm.a"
+        );
+        assert_eq!(
+            decode_tagged(packed("ilotrmapdns", 5) << 4 | 11).unwrap(),
+            "ilotrmapdns"
+        );
+        assert!(matches!(
+            decode_tagged(12),
+            Err(MemoryReadError::InvalidLength { length: 12, .. })
+        ));
+    }
+
+    #[test]
+    fn extracts_tagged_nsstring_payload() {
+        let payload = 0x0616_2613u64;
+        let obfuscator = 0x5a5a_0000_1234_5670u64;
+
+        let low_bit = (payload << 4 | NSSTRING_TAG << 1 | 1) ^ obfuscator;
+        assert!(TaggedPointers::LowBit.is_tagged(low_bit));
+        assert_eq!(
+            TaggedPointers::LowBit.nsstring_payload(low_bit, obfuscator, &IDENTITY),
+            Some(payload)
+        );
+
+        // The runtime stores the permuted index of the class.
+        let permutations = [4, 6, 5, 0, 1, 3, 2, 7];
+        let split = (1 << 63 | payload << 3 | 5) ^ (obfuscator & !7);
+        assert!(TaggedPointers::Split.is_tagged(split));
+        assert_eq!(
+            TaggedPointers::Split.nsstring_payload(split, obfuscator & !7, &permutations),
+            Some(payload)
+        );
+        assert_eq!(
+            TaggedPointers::Split.nsstring_payload(split, obfuscator & !7, &IDENTITY),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_constant_cfstring() {
+        let mut bytes = vec![0u8; 0x50];
+        bytes[8..16].copy_from_slice(&0x7c8u64.to_le_bytes());
+        bytes[16..24].copy_from_slice(&(BASE as u64 + 0x40).to_le_bytes());
+        bytes[24..32].copy_from_slice(&5u64.to_le_bytes());
+        bytes[0x40..0x45].copy_from_slice(b"hello");
+        let process = MockProcess::new()
+            .with_region(BASE, bytes)
+            .with_pointer_size(8);
+
+        assert_eq!(read_cfstring(&process, BASE).unwrap(), "hello");
+    }
+
+    #[test]
+    fn reads_inline_cfstring_with_length_byte() {
+        let mut bytes = vec![0u8; 0x20];
+        bytes[8..16].copy_from_slice(&(0x700 | HAS_LENGTH_BYTE as u64).to_le_bytes());
+        bytes[16..20].copy_from_slice(b"\x03abc");
+        let process = MockProcess::new()
+            .with_region(BASE, bytes)
+            .with_pointer_size(8);
+
+        assert_eq!(read_cfstring(&process, BASE).unwrap(), "abc");
+    }
+
+    #[test]
+    fn reads_mutable_unicode_cfstring() {
+        let mut bytes = vec![0u8; 0x50];
+        let flags = 0x700 | (CONTENTS_MASK | IS_UNICODE | IS_MUTABLE) as u64;
+        bytes[8..16].copy_from_slice(&flags.to_le_bytes());
+        bytes[16..24].copy_from_slice(&(BASE as u64 + 0x40).to_le_bytes());
+        bytes[24..32].copy_from_slice(&2u64.to_le_bytes());
+        bytes[0x40..0x44].copy_from_slice(&[0xe9, 0, 0x1f, 0x4e]);
+        let process = MockProcess::new()
+            .with_region(BASE, bytes)
+            .with_pointer_size(8);
+
+        assert_eq!(read_cfstring(&process, BASE).unwrap(), "é\u{4e1f}");
+    }
+
+    #[test]
+    fn rejects_objects_of_other_types() {
+        let mut bytes = vec![0u8; 0x20];
+        bytes[8..16].copy_from_slice(&0x1c8u64.to_le_bytes());
+        let process = MockProcess::new()
+            .with_region(BASE, bytes)
+            .with_pointer_size(8);
+
+        assert!(matches!(
+            read_cfstring(&process, BASE),
+            Err(MemoryReadError::InvalidFormat {
+                reason: "object is not a CFString"
+            })
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+//! One-shot reads that open the process, read a single value and close it again.
+
+use crate::{offset_address, open_process, MemoryReadError, Process};
+
+#[cfg(target_os = "linux")]
+type PlatformProcess = crate::LinuxProcess;
+#[cfg(target_os = "windows")]
+type PlatformProcess = crate::WindowsProcess;
+#[cfg(target_os = "macos")]
+type PlatformProcess = crate::MacosProcess;
+
+/// Opens the process, resolves the module base and reads with `read` at `base + offset`.
+fn read_once<T, F>(pid: u32, module: &str, offset: usize, read: F) -> Result<T, MemoryReadError>
+where
+    F: FnOnce(&PlatformProcess, usize) -> Result<T, MemoryReadError>,
+{
+    let process = open_process(pid).ok_or(MemoryReadError::ProcessNotFound { pid })?;
+    let base = process
+        .base_address(module)
+        .ok_or_else(|| MemoryReadError::ModuleNotFound {
+            name: module.to_string(),
+        })?;
+
+    read(&process, offset_address(base, offset)?)
+}
+
+/// Reads `len` bytes at `offset` from the base of the module.
+pub fn read_once_bytes(
+    pid: u32,
+    module: &str,
+    offset: usize,
+    len: usize,
+) -> Result<Vec<u8>, MemoryReadError> {
+    read_once(pid, module, offset, |process, address| {
+        process.read_bytes_vec(address, len)
+    })
+}
+
+macro_rules! define_number_read_once (
+    ($($name: ident, $read: ident, $type: ty);*) => (
+        $(
+            #[doc = concat!("Reads `", stringify!($type), "` at `offset` from the base of the module.")]
+            pub fn $name(pid: u32, module: &str, offset: usize) -> Result<$type, MemoryReadError> {
+                read_once(pid, module, offset, |process, address| process.$read(address))
+            }
+        )*
+    );
+);
+
+define_number_read_once!(
+    read_once_u8, read_u8, u8;
+    read_once_u32, read_u32, u32;
+    read_once_u64, read_u64, u64;
+    read_once_i32, read_i32, i32;
+    read_once_i64, read_i64, i64;
+    read_once_f32, read_f32, f32;
+    read_once_f64, read_f64, f64
+);
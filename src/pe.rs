@@ -0,0 +1,257 @@
+use crate::{Endianness, MemoryReadError, Process, PAGE_SIZE};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Longest export name read from the name table.
+const MAX_EXPORT_NAME: usize = 4096;
+
+/// Index of the import table in the data directories.
+const IMPORT_DIRECTORY: usize = 1;
+
+const IMPORT_DESCRIPTOR_SIZE: usize = 20;
+
+/// Most functions read from the import lookup table of a single DLL.
+const MAX_IMPORTS: usize = 65536;
+
+fn invalid(reason: &'static str) -> MemoryReadError {
+    MemoryReadError::InvalidFormat { reason }
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut word = [0u8; 4];
+    word.copy_from_slice(&bytes[offset..offset + 4]);
+
+    u32::from_le_bytes(word)
+}
+
+/// Address of the NT headers of the PE image mapped at `base`, checking their signature.
+fn nt_headers<P: Process + ?Sized>(process: &P, base: usize) -> Result<usize, MemoryReadError> {
+    let read_u32 = |address| process.read_u32_with_order(address, Endianness::Little);
+
+    let nt_headers = base + read_u32(base + 0x3c)? as usize;
+
+    if read_u32(nt_headers)? != u32::from_le_bytes(*b"PE\0\0") {
+        return Err(invalid("missing PE signature"));
+    }
+
+    Ok(nt_headers)
+}
+
+/// Address of the data directory table of the PE image mapped at `base` and whether the
+/// image is PE32+.
+fn data_directories<P: Process + ?Sized>(
+    process: &P,
+    base: usize,
+) -> Result<(usize, bool), MemoryReadError> {
+    let optional_header = nt_headers(process, base)? + 24;
+    let magic = process.read_bytes_vec(optional_header, 2)?;
+
+    // The data directories follow the optional header fields, which are wider in PE32+.
+    match u16_at(&magic, 0) {
+        0x10b => Ok((optional_header + 96, false)),
+        0x20b => Ok((optional_header + 112, true)),
+        _ => Err(invalid("unknown optional header magic")),
+    }
+}
+
+/// Reads `ImageBase` of the optional header of the PE file.
+pub(crate) fn preferred_base(path: &Path) -> Result<usize, MemoryReadError> {
+    // The headers fit in the first page of the file.
+    let mut headers = Vec::new();
+    File::open(path)?
+        .take(PAGE_SIZE as u64)
+        .read_to_end(&mut headers)?;
+
+    let field = |offset: usize, len: usize| {
+        headers
+            .get(offset..offset + len)
+            .ok_or_else(|| invalid("truncated PE headers"))
+    };
+
+    let nt_headers = u32_at(field(0x3c, 4)?, 0) as usize;
+
+    if field(nt_headers, 4)? != b"PE\0\0" {
+        return Err(invalid("missing PE signature"));
+    }
+
+    let optional_header = nt_headers + 24;
+
+    match u16_at(field(optional_header, 2)?, 0) {
+        0x10b => Ok(u32_at(field(optional_header + 28, 4)?, 0) as usize),
+        0x20b => {
+            let mut image_base = [0u8; 8];
+            image_base.copy_from_slice(field(optional_header + 24, 8)?);
+
+            Ok(u64::from_le_bytes(image_base) as usize)
+        }
+        _ => Err(invalid("unknown optional header magic")),
+    }
+}
+
+/// Reads `TimeDateStamp` of the file header of the PE image mapped at `base`.
+#[cfg(target_os = "windows")]
+pub(crate) fn timestamp<P: Process + ?Sized>(
+    process: &P,
+    base: usize,
+) -> Result<u32, MemoryReadError> {
+    process.read_u32_with_order(nt_headers(process, base)? + 8, Endianness::Little)
+}
+
+/// Reads exports of the PE image mapped at `base` with their absolute addresses.
+///
+/// Exports without a name are listed as `#<ordinal>`. Forwarded exports are skipped, as their
+/// code lives in another module.
+pub(crate) fn exports<P: Process + ?Sized>(
+    process: &P,
+    base: usize,
+) -> Result<Vec<(String, usize)>, MemoryReadError> {
+    let read_u32 = |address| process.read_u32_with_order(address, Endianness::Little);
+
+    let (export_directory_entry, _) = data_directories(process, base)?;
+
+    let directory_rva = read_u32(export_directory_entry)? as usize;
+    let directory_size = read_u32(export_directory_entry + 4)? as usize;
+
+    if directory_rva == 0 {
+        return Ok(Vec::new());
+    }
+
+    let directory = process.read_bytes_vec(base + directory_rva, 40)?;
+    let function_count = u32_at(&directory, 20) as usize;
+    let name_count = u32_at(&directory, 24) as usize;
+
+    let functions = process.read_bytes_vec(
+        base + u32_at(&directory, 28) as usize,
+        function_count.saturating_mul(4),
+    )?;
+    let names = process.read_bytes_vec(
+        base + u32_at(&directory, 32) as usize,
+        name_count.saturating_mul(4),
+    )?;
+    let ordinals = process.read_bytes_vec(
+        base + u32_at(&directory, 36) as usize,
+        name_count.saturating_mul(2),
+    )?;
+
+    let mut exports = Vec::with_capacity(name_count);
+
+    for index in 0..name_count {
+        let ordinal = u16_at(&ordinals, index * 2) as usize;
+
+        if ordinal >= function_count {
+            return Err(invalid("export ordinal out of range"));
+        }
+
+        let function_rva = u32_at(&functions, ordinal * 4) as usize;
+
+        if function_rva >= directory_rva && function_rva < directory_rva + directory_size {
+            continue;
+        }
+
+        let name_address = base + u32_at(&names, index * 4) as usize;
+        let name = process.read_string_chunked(name_address, 64, MAX_EXPORT_NAME)?;
+
+        exports.push((name, base + function_rva));
+    }
+
+    let ordinal_base = u32_at(&directory, 16) as usize;
+    let mut named = vec![false; function_count];
+
+    for index in 0..name_count {
+        named[u16_at(&ordinals, index * 2) as usize] = true;
+    }
+
+    for (index, _) in named.iter().enumerate().filter(|(_, &named)| !named) {
+        let function_rva = u32_at(&functions, index * 4) as usize;
+
+        // Unused slots of the address table are zero.
+        if function_rva == 0
+            || (function_rva >= directory_rva && function_rva < directory_rva + directory_size)
+        {
+            continue;
+        }
+
+        exports.push((format!("#{}", ordinal_base + index), base + function_rva));
+    }
+
+    Ok(exports)
+}
+
+/// Reads `(dll, function, address)` of every import of the PE image mapped at `base`, the
+/// address being the current value of its import address table slot.
+///
+/// Functions imported by ordinal are named `#<ordinal>`.
+pub(crate) fn imports<P: Process + ?Sized>(
+    process: &P,
+    base: usize,
+) -> Result<Vec<(String, String, usize)>, MemoryReadError> {
+    let read_u32 = |address| process.read_u32_with_order(address, Endianness::Little);
+    let read_u64 = |address| process.read_u64_with_order(address, Endianness::Little);
+
+    let (data_directories, is_pe32_plus) = data_directories(process, base)?;
+    let directory_rva = read_u32(data_directories + IMPORT_DIRECTORY * 8)? as usize;
+
+    if directory_rva == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (thunk_size, ordinal_flag) = if is_pe32_plus {
+        (8, 1 << 63)
+    } else {
+        (4, 1 << 31)
+    };
+    let read_thunk = |address| {
+        if is_pe32_plus {
+            read_u64(address)
+        } else {
+            read_u32(address).map(u64::from)
+        }
+    };
+
+    let mut imports = Vec::new();
+
+    for descriptor in (base + directory_rva..).step_by(IMPORT_DESCRIPTOR_SIZE) {
+        let descriptor = process.read_bytes_vec(descriptor, IMPORT_DESCRIPTOR_SIZE)?;
+        let name_rva = u32_at(&descriptor, 12) as usize;
+        let address_table = base + u32_at(&descriptor, 16) as usize;
+
+        // The descriptor list ends with a zeroed entry.
+        if name_rva == 0 {
+            break;
+        }
+
+        // Bound images may lack the lookup table, leaving only the address table with names.
+        let lookup_table = match u32_at(&descriptor, 0) as usize {
+            0 => address_table,
+            rva => base + rva,
+        };
+
+        let dll = process.read_string_chunked(base + name_rva, 64, MAX_EXPORT_NAME)?;
+
+        for index in 0..MAX_IMPORTS {
+            let lookup = read_thunk(lookup_table + index * thunk_size)?;
+
+            if lookup == 0 {
+                break;
+            }
+
+            let function = if lookup & ordinal_flag != 0 {
+                format!("#{}", lookup & 0xffff)
+            } else {
+                // The name is preceded by a `u16` hint.
+                let name_address = base + (lookup & 0x7fff_ffff) as usize + 2;
+                process.read_string_chunked(name_address, 64, MAX_EXPORT_NAME)?
+            };
+
+            let address = read_thunk(address_table + index * thunk_size)? as usize;
+            imports.push((dll.clone(), function, address));
+        }
+    }
+
+    Ok(imports)
+}
@@ -0,0 +1,65 @@
+use crate::Endianness;
+use std::mem::{size_of_val, zeroed as zeroed_value};
+use std::slice;
+
+/// Plain old data that can be filled directly from process memory bytes.
+///
+/// # Safety
+///
+/// Every bit pattern must be a valid value of the type and the type must not contain padding.
+/// Structs should be `#[repr(C)]` (or `#[repr(C, packed)]`) and built only from `Pod` fields.
+pub unsafe trait Pod: Copy + 'static {
+    /// Converts value read as raw bytes stored in `order` into the host byte order.
+    ///
+    /// Numbers and arrays of them swap bytes when `order` differs from the host. The default
+    /// returns the value untouched, so structs read from a target of other endianness have to
+    /// convert their fields themselves.
+    fn to_host_order(self, _order: Endianness) -> Self {
+        self
+    }
+}
+
+macro_rules! impl_pod (
+    ($($type: ty),*) => (
+        $(unsafe impl Pod for $type {
+            fn to_host_order(self, order: Endianness) -> Self {
+                match order {
+                    Endianness::Little => <$type>::from_le(self),
+                    Endianness::Big => <$type>::from_be(self),
+                }
+            }
+        })*
+    );
+);
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+unsafe impl Pod for f32 {
+    fn to_host_order(self, order: Endianness) -> Self {
+        f32::from_bits(self.to_bits().to_host_order(order))
+    }
+}
+
+unsafe impl Pod for f64 {
+    fn to_host_order(self, order: Endianness) -> Self {
+        f64::from_bits(self.to_bits().to_host_order(order))
+    }
+}
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+    fn to_host_order(self, order: Endianness) -> Self {
+        self.map(|value| value.to_host_order(order))
+    }
+}
+
+/// Byte view of a `Pod` slice.
+pub(crate) fn bytes_of_slice_mut<T: Pod>(values: &mut [T]) -> &mut [u8] {
+    let len = size_of_val(values);
+
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, len) }
+}
+
+/// All-zero value, which is valid for every `Pod` type.
+pub(crate) fn zeroed<T: Pod>() -> T {
+    unsafe { zeroed_value() }
+}
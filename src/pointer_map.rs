@@ -0,0 +1,129 @@
+use crate::region::find_region;
+use crate::scan::{RegionStreamer, SCAN_CHUNK_SIZE};
+use crate::{pointer_from_bytes, MemoryReadError, MemoryRegion, Module, Process};
+use std::ops::ControlFlow;
+
+/// Pointer-sized slots of the target that point into readable memory.
+#[derive(Debug, Clone)]
+pub struct PointerMap {
+    /// `(address, value)` pairs sorted by value.
+    pointers: Vec<(usize, usize)>,
+    modules: Vec<Module>,
+}
+
+/// Offset chain leading from a module static to a target address.
+///
+/// Reading the pointer at `module + module_offset`, then repeatedly adding the next offset and
+/// dereferencing, ends at the target once the last offset is added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerPath {
+    pub module: String,
+    pub module_offset: usize,
+    pub offsets: Vec<usize>,
+}
+
+impl PointerMap {
+    pub(crate) fn build<P: Process + ?Sized>(
+        process: &P,
+        alignment: usize,
+    ) -> Result<Self, MemoryReadError> {
+        if alignment == 0 {
+            return Err(MemoryReadError::InvalidLength {
+                length: alignment,
+                max: usize::MAX,
+            });
+        }
+
+        let pointer_size = process.pointer_size()?;
+        let order = process.endianness();
+        let regions: Vec<MemoryRegion> = process
+            .memory_regions()?
+            .into_iter()
+            .filter(|region| region.readable)
+            .collect();
+        let modules = process.modules()?;
+
+        let streamer = RegionStreamer::new(
+            SCAN_CHUNK_SIZE.min(process.max_read_size()),
+            pointer_size - 1,
+        );
+        let mut pointers = Vec::new();
+
+        streamer.stream(process, &regions, |address, bytes| {
+            let mut offset = (alignment - address % alignment) % alignment;
+
+            while offset + pointer_size <= bytes.len() {
+                let value = pointer_from_bytes(&bytes[offset..offset + pointer_size], order);
+
+                if find_region(&regions, value).is_some() {
+                    pointers.push((address + offset, value));
+                }
+
+                offset += alignment;
+            }
+
+            ControlFlow::Continue(())
+        });
+
+        pointers.sort_unstable_by_key(|&(_, value)| value);
+
+        Ok(PointerMap { pointers, modules })
+    }
+
+    /// Number of recorded pointers.
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty()
+    }
+
+    /// Returns `(address, value)` of every slot whose value lies within `start..=end`.
+    pub fn pointers_to(&self, start: usize, end: usize) -> &[(usize, usize)] {
+        let from = self.pointers.partition_point(|&(_, value)| value < start);
+        let to = self.pointers.partition_point(|&(_, value)| value <= end);
+
+        &self.pointers[from..to.max(from)]
+    }
+
+    /// Finds offset chains of at most `max_depth` levels from module statics to the target.
+    ///
+    /// At every level the pointer may point up to `max_offset` bytes before the next address.
+    pub fn paths_to(&self, target: usize, max_depth: usize, max_offset: usize) -> Vec<PointerPath> {
+        let mut paths = Vec::new();
+        let mut offsets = Vec::new();
+        self.collect_paths(target, max_depth, max_offset, &mut offsets, &mut paths);
+
+        paths
+    }
+
+    /// Walks backwards from `target`, keeping offsets from innermost to outermost.
+    fn collect_paths(
+        &self,
+        target: usize,
+        depth: usize,
+        max_offset: usize,
+        offsets: &mut Vec<usize>,
+        paths: &mut Vec<PointerPath>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+
+        for &(address, value) in self.pointers_to(target.saturating_sub(max_offset), target) {
+            offsets.push(target - value);
+
+            match self.modules.iter().find(|module| module.contains(address)) {
+                Some(module) => paths.push(PointerPath {
+                    module: module.name.clone(),
+                    module_offset: address - module.base,
+                    offsets: offsets.iter().rev().copied().collect(),
+                }),
+                None => self.collect_paths(address, depth - 1, max_offset, offsets, paths),
+            }
+
+            offsets.pop();
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    Endianness, HashMapLayout, MemoryReadError, MemoryRegion, Module, ModuleExports, Pod,
+    PointerMap, Process, RbTreeLayout, SpanLayout,
+};
+use std::mem::MaybeUninit;
+use std::ops::ControlFlow;
+
+/// View of a process that translates the addresses passed to its readers, created by
+/// `Process::rebased`.
+///
+/// Only the addresses the caller supplies are translated. Readers following pointers, such as
+/// `read_pointer_chain`, translate their starting address and read the pointers they find as
+/// they are, since those already are addresses of the running process. Addresses such readers
+/// return are therefore ones of the inner process, to be read through `inner`.
+///
+/// Module, region and pointer queries are passed through as is, and so are the readers working
+/// on the addresses they return, such as `exports` and `build_pointer_map`. `find_value` and
+/// `read_function` translate their start but return addresses of the inner process.
+pub struct Rebased<'a, P: ?Sized, F> {
+    process: &'a P,
+    rebase: F,
+}
+
+impl<'a, P: Process + ?Sized, F: Fn(usize) -> usize> Rebased<'a, P, F> {
+    pub(crate) fn new(process: &'a P, rebase: F) -> Self {
+        Rebased { process, rebase }
+    }
+
+    /// Process the reads are forwarded to.
+    pub fn inner(&self) -> &'a P {
+        self.process
+    }
+}
+
+/// Forwards `read_*_deref` to the inner process with the pointer address translated.
+macro_rules! forward_deref_read (
+    ($type: ident, $name: ident) => (
+        fn $name(&self, pointer_address: usize, offset: usize) -> Result<$type, MemoryReadError> {
+            self.process.$name((self.rebase)(pointer_address), offset)
+        }
+    );
+);
+
+impl<P: Process + ?Sized, F: Fn(usize) -> usize> Process for Rebased<'_, P, F> {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        self.process.base_address(module_name)
+    }
+
+    fn main_module_base(&self) -> Option<usize> {
+        self.process.main_module_base()
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        self.process.read_bytes((self.rebase)(address), buffer)
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        self.process
+            .read_bytes_partial((self.rebase)(address), buffer)
+    }
+
+    fn read_bytes_uninit<'b>(
+        &self,
+        address: usize,
+        buffer: &'b mut [MaybeUninit<u8>],
+    ) -> Result<&'b mut [u8], MemoryReadError> {
+        self.process
+            .read_bytes_uninit((self.rebase)(address), buffer)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        self.process.memory_regions()
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        self.process.modules()
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.process.endianness()
+    }
+
+    fn max_read_size(&self) -> usize {
+        self.process.max_read_size()
+    }
+
+    fn page_size(&self) -> usize {
+        self.process.page_size()
+    }
+
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        self.process.pointer_size()
+    }
+
+    fn is_plausible_pointer(&self, value: usize) -> bool {
+        self.process.is_plausible_pointer(value)
+    }
+
+    fn exports(&self, module_name: &str) -> Result<Vec<(String, usize)>, MemoryReadError> {
+        self.process.exports(module_name)
+    }
+
+    fn module_exports(&self, module_name: &str) -> Result<ModuleExports, MemoryReadError> {
+        self.process.module_exports(module_name)
+    }
+
+    fn get_export(&self, module_name: &str, export: &str) -> Result<usize, MemoryReadError> {
+        self.process.get_export(module_name, export)
+    }
+
+    fn read_export_u64(&self, module_name: &str, export: &str) -> Result<u64, MemoryReadError> {
+        self.process.read_export_u64(module_name, export)
+    }
+
+    fn read_iat(&self, module_name: &str) -> Result<Vec<(String, String, usize)>, MemoryReadError> {
+        self.process.read_iat(module_name)
+    }
+
+    fn module_preferred_base(&self, module_name: &str) -> Result<usize, MemoryReadError> {
+        self.process.module_preferred_base(module_name)
+    }
+
+    fn module_relocation_delta(&self, module_name: &str) -> Result<isize, MemoryReadError> {
+        self.process.module_relocation_delta(module_name)
+    }
+
+    fn read_function(&self, address: usize, max_len: usize) -> Result<Vec<u8>, MemoryReadError> {
+        self.process.read_function((self.rebase)(address), max_len)
+    }
+
+    fn scan_value_cb<T, C>(&self, value: T, on_match: C) -> Result<(), MemoryReadError>
+    where
+        T: Pod + PartialEq,
+        C: FnMut(usize) -> ControlFlow<()>,
+    {
+        self.process.scan_value_cb(value, on_match)
+    }
+
+    fn find_value<T: Pod + PartialEq>(
+        &self,
+        start: usize,
+        len: usize,
+        value: T,
+    ) -> Result<Vec<usize>, MemoryReadError> {
+        self.process.find_value((self.rebase)(start), len, value)
+    }
+
+    fn build_pointer_map(&self, alignment: usize) -> Result<PointerMap, MemoryReadError> {
+        self.process.build_pointer_map(alignment)
+    }
+
+    fn read_string_table(
+        &self,
+        array_address: usize,
+        count: usize,
+    ) -> Result<Vec<String>, MemoryReadError> {
+        self.process
+            .read_string_table((self.rebase)(array_address), count)
+    }
+
+    fn read_vfunc(
+        &self,
+        object_address: usize,
+        vtable_index: usize,
+    ) -> Result<usize, MemoryReadError> {
+        self.process
+            .read_vfunc((self.rebase)(object_address), vtable_index)
+    }
+
+    fn read_branch_target(
+        &self,
+        instruction_address: usize,
+    ) -> Result<Option<usize>, MemoryReadError> {
+        self.process
+            .read_branch_target((self.rebase)(instruction_address))
+    }
+
+    fn read_pointer_chain(&self, base: usize, offsets: &[usize]) -> Result<usize, MemoryReadError> {
+        self.process
+            .read_pointer_chain((self.rebase)(base), offsets)
+    }
+
+    fn try_read_pointer_chain(
+        &self,
+        base: usize,
+        offsets: &[usize],
+    ) -> Result<Option<usize>, MemoryReadError> {
+        self.process
+            .try_read_pointer_chain((self.rebase)(base), offsets)
+    }
+
+    fn read_chain<T: Pod>(&self, base: usize, offsets: &[usize]) -> Result<T, MemoryReadError> {
+        self.process.read_chain((self.rebase)(base), offsets)
+    }
+
+    fn read_linked_list(
+        &self,
+        head: usize,
+        next_offset: usize,
+        max_nodes: usize,
+    ) -> Result<Vec<usize>, MemoryReadError> {
+        self.process
+            .read_linked_list((self.rebase)(head), next_offset, max_nodes)
+    }
+
+    fn read_hashmap<K: Pod, V: Pod>(
+        &self,
+        address: usize,
+        layout: HashMapLayout,
+    ) -> Result<Vec<(K, V)>, MemoryReadError> {
+        self.process.read_hashmap((self.rebase)(address), layout)
+    }
+
+    fn read_rbtree<K: Pod, V: Pod>(
+        &self,
+        root: usize,
+        layout: RbTreeLayout,
+    ) -> Result<Vec<(K, V)>, MemoryReadError> {
+        self.process.read_rbtree((self.rebase)(root), layout)
+    }
+
+    fn read_span<T: Pod>(&self, span_address: usize) -> Result<Vec<T>, MemoryReadError> {
+        self.process.read_span((self.rebase)(span_address))
+    }
+
+    fn read_span_with_layout<T: Pod>(
+        &self,
+        span_address: usize,
+        layout: SpanLayout,
+    ) -> Result<Vec<T>, MemoryReadError> {
+        self.process
+            .read_span_with_layout((self.rebase)(span_address), layout)
+    }
+
+    fn read_rust_vec<T: Pod>(&self, address: usize) -> Result<Vec<T>, MemoryReadError> {
+        self.process.read_rust_vec((self.rebase)(address))
+    }
+
+    forward_deref_read!(u32, read_u32_deref);
+    forward_deref_read!(u64, read_u64_deref);
+    forward_deref_read!(u128, read_u128_deref);
+    forward_deref_read!(i32, read_i32_deref);
+    forward_deref_read!(i64, read_i64_deref);
+    forward_deref_read!(f32, read_f32_deref);
+    forward_deref_read!(f64, read_f64_deref);
+}
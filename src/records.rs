@@ -0,0 +1,69 @@
+use crate::{offset_address, pod, MemoryReadError, Pod, Process};
+use std::mem::size_of;
+
+/// Records fetched by a single read.
+const RECORDS_PER_READ: usize = 64;
+
+/// Iterator over consecutive `T` records, created by `Process::iter_records`.
+///
+/// Records are read lazily in batches; iteration ends after the first failed read.
+pub struct Records<'a, P: ?Sized, T> {
+    process: &'a P,
+    address: usize,
+    remaining: usize,
+    batch: Vec<T>,
+    position: usize,
+    failed: bool,
+}
+
+impl<'a, P: Process + ?Sized, T: Pod> Records<'a, P, T> {
+    pub(crate) fn new(process: &'a P, address: usize, count: usize) -> Self {
+        Records {
+            process,
+            address,
+            remaining: count,
+            batch: Vec::new(),
+            position: 0,
+            failed: false,
+        }
+    }
+
+    fn read_batch(&mut self) -> Result<(), MemoryReadError> {
+        let len = self.remaining.min(RECORDS_PER_READ);
+
+        self.batch.clear();
+        self.batch.resize(len, pod::zeroed());
+        self.position = 0;
+        self.process
+            .read_into_slice(self.address, &mut self.batch)?;
+
+        self.address = offset_address(self.address, len * size_of::<T>())?;
+        self.remaining -= len;
+
+        Ok(())
+    }
+}
+
+impl<P: Process + ?Sized, T: Pod> Iterator for Records<'_, P, T> {
+    type Item = Result<T, MemoryReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.batch.len() {
+            if self.failed || self.remaining == 0 {
+                return None;
+            }
+
+            if let Err(error) = self.read_batch() {
+                self.failed = true;
+                self.batch.clear();
+
+                return Some(Err(error));
+            }
+        }
+
+        let record = self.batch[self.position];
+        self.position += 1;
+
+        Some(Ok(record))
+    }
+}
@@ -0,0 +1,116 @@
+use crate::MemoryReadError;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// What backs a memory region.
+///
+/// Windows only tells file-backed and private memory apart, so it reports `File` and `Anonymous`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RegionKind {
+    Heap,
+    Stack,
+    /// Mapping of a file, such as a loaded binary.
+    File(PathBuf),
+    Anonymous,
+    Vdso,
+    /// Other kernel provided mapping, such as Linux `[vvar]`.
+    Special(String),
+}
+
+/// Committed range of the target's address space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub base: usize,
+    pub size: usize,
+    pub readable: bool,
+    pub writable: bool,
+    pub executable: bool,
+    pub kind: RegionKind,
+}
+
+impl MemoryRegion {
+    /// First address past the end of the region.
+    pub fn end(&self) -> usize {
+        self.base.saturating_add(self.size)
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.base && address < self.end()
+    }
+}
+
+/// Finds region containing the address in regions sorted by base address.
+pub(crate) fn find_region(regions: &[MemoryRegion], address: usize) -> Option<&MemoryRegion> {
+    let index = match regions.binary_search_by(|region| region.base.cmp(&address)) {
+        Ok(index) => index,
+        Err(0) => return None,
+        Err(index) => index - 1,
+    };
+
+    regions.get(index).filter(|region| region.contains(address))
+}
+
+/// How long the region list cached for `is_plausible_pointer` is trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CachePolicy {
+    /// Regions are loaded again for every check.
+    Never,
+    /// Cached regions are reloaded once they are older than the duration.
+    Ttl(Duration),
+    /// Regions are loaded on first use and only reloaded by `refresh_regions`.
+    #[default]
+    Manual,
+}
+
+/// Lazily loaded copy of the target's regions for cheap address checks.
+#[derive(Debug, Default)]
+pub(crate) struct RegionCache {
+    regions: Mutex<Option<(Vec<MemoryRegion>, Instant)>>,
+    policy: CachePolicy,
+}
+
+impl RegionCache {
+    pub(crate) fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: CachePolicy) {
+        self.policy = policy;
+    }
+
+    /// Replaces cached regions with freshly loaded ones.
+    pub(crate) fn refresh<F>(&self, load: F) -> Result<(), MemoryReadError>
+    where
+        F: FnOnce() -> Result<Vec<MemoryRegion>, MemoryReadError>,
+    {
+        let regions = load()?;
+        *self.regions.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((regions, Instant::now()));
+
+        Ok(())
+    }
+
+    /// Checks whether the address is readable, loading regions when the policy asks for it.
+    pub(crate) fn is_readable<F>(&self, address: usize, load: F) -> bool
+    where
+        F: FnOnce() -> Result<Vec<MemoryRegion>, MemoryReadError>,
+    {
+        let mut regions = self.regions.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let expired = match (&*regions, self.policy) {
+            (None, _) | (_, CachePolicy::Never) => true,
+            (Some((_, loaded)), CachePolicy::Ttl(ttl)) => loaded.elapsed() >= ttl,
+            (Some(_), CachePolicy::Manual) => false,
+        };
+
+        if expired {
+            *regions = load().ok().map(|loaded| (loaded, Instant::now()));
+        }
+
+        regions
+            .as_ref()
+            .and_then(|(regions, _)| find_region(regions, address))
+            .is_some_and(|region| region.readable)
+    }
+}
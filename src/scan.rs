@@ -0,0 +1,173 @@
+use crate::{offset_address, pod, MemoryReadError, MemoryRegion, Pod, Process};
+use std::mem::{align_of, size_of};
+use std::ops::{ControlFlow, Range};
+
+/// Size of the chunks regions are read in while scanning.
+pub(crate) const SCAN_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Reads regions in fixed-size windows for scanning.
+///
+/// Consecutive windows of a region share `overlap` bytes, so with an overlap of `len - 1` every
+/// `len`-byte match is seen exactly once, including matches straddling a window boundary.
+pub(crate) struct RegionStreamer {
+    chunk_size: usize,
+    overlap: usize,
+}
+
+impl RegionStreamer {
+    pub(crate) fn new(chunk_size: usize, overlap: usize) -> Self {
+        RegionStreamer {
+            chunk_size: chunk_size.max(overlap + 1),
+            overlap,
+        }
+    }
+
+    /// Calls `visit` with the address and bytes of every readable window of the regions, until
+    /// it breaks.
+    ///
+    /// Failed reads are skipped, and a short read yields only the bytes actually read.
+    pub(crate) fn stream<P, F>(&self, process: &P, regions: &[MemoryRegion], mut visit: F)
+    where
+        P: Process + ?Sized,
+        F: FnMut(usize, &[u8]) -> ControlFlow<()>,
+    {
+        let mut buffer = vec![0u8; self.chunk_size];
+
+        for region in regions {
+            let mut address = region.base;
+
+            while address < region.end() {
+                let len = self.chunk_size.min(region.end() - address);
+                let window = &mut buffer[..len];
+
+                let read = process.read_bytes_partial(address, window).unwrap_or(0);
+
+                if read > 0 && visit(address, &window[..read]).is_break() {
+                    return;
+                }
+
+                if read == len && address + len < region.end() {
+                    address += len - self.overlap;
+                } else {
+                    address += len;
+                }
+            }
+        }
+    }
+}
+
+/// Calls `on_match` with the address of every `align_of::<T>()` aligned value of readable memory
+/// in `range` equal to `value`, until it breaks.
+pub(crate) fn scan_value<P, T, F>(
+    process: &P,
+    range: Range<usize>,
+    value: T,
+    mut on_match: F,
+) -> Result<(), MemoryReadError>
+where
+    P: Process + ?Sized,
+    T: Pod + PartialEq,
+    F: FnMut(usize) -> ControlFlow<()>,
+{
+    let size = size_of::<T>();
+    let alignment = align_of::<T>();
+    let order = process.endianness();
+    let regions: Vec<MemoryRegion> = process
+        .memory_regions()?
+        .into_iter()
+        .filter(|region| region.readable)
+        .filter_map(|region| {
+            let base = region.base.max(range.start);
+            let end = region.end().min(range.end);
+
+            (base < end).then(|| MemoryRegion {
+                base,
+                size: end - base,
+                ..region
+            })
+        })
+        .collect();
+
+    let streamer = RegionStreamer::new(
+        SCAN_CHUNK_SIZE.min(process.max_read_size()),
+        size.saturating_sub(1),
+    );
+
+    streamer.stream(process, &regions, |address, bytes| {
+        let mut offset = (alignment - address % alignment) % alignment;
+        let mut candidate = [pod::zeroed::<T>()];
+
+        while offset + size <= bytes.len() {
+            pod::bytes_of_slice_mut(&mut candidate).copy_from_slice(&bytes[offset..offset + size]);
+
+            if candidate[0].to_host_order(order) == value {
+                on_match(address + offset)?;
+            }
+
+            offset += alignment;
+        }
+
+        ControlFlow::Continue(())
+    });
+
+    Ok(())
+}
+
+/// Parses byte pattern such as `"48 8B ?? ?? 89"`, where `??` (or `?`) marks a wildcard byte.
+///
+/// Returns `None` when a token is neither a hex byte nor a wildcard.
+pub fn parse_pattern(pattern: &str) -> Option<Vec<Option<u8>>> {
+    pattern
+        .split_whitespace()
+        .map(|token| match token {
+            "?" | "??" => Some(None),
+            _ if token.len() == 2 => u8::from_str_radix(token, 16).ok().map(Some),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Finds the first match of the pattern, `None` entries matching any byte, in the `len` bytes
+/// from `start`.
+pub(crate) fn find_pattern<P: Process + ?Sized>(
+    process: &P,
+    start: usize,
+    len: usize,
+    pattern: &[Option<u8>],
+) -> Result<Option<usize>, MemoryReadError> {
+    if pattern.is_empty() {
+        return Ok(Some(start));
+    }
+
+    let end = offset_address(start, len)?;
+    let chunk_size = SCAN_CHUNK_SIZE
+        .min(process.max_read_size())
+        .max(pattern.len());
+    let mut buffer = vec![0u8; chunk_size.min(len)];
+    let mut address = start;
+
+    let matches = |window: &[u8]| {
+        window
+            .iter()
+            .zip(pattern)
+            .all(|(byte, expected)| expected.is_none_or(|expected| *byte == expected))
+    };
+
+    // Consecutive chunks share `pattern.len() - 1` bytes, so matches across them are found.
+    while end - address >= pattern.len() {
+        let chunk = &mut buffer[..chunk_size.min(end - address)];
+        process.read_bytes(address, chunk)?;
+
+        if let Some(offset) = chunk.windows(pattern.len()).position(matches) {
+            return Ok(Some(address + offset));
+        }
+
+        if address + chunk.len() == end {
+            break;
+        }
+
+        address += chunk.len() - (pattern.len() - 1);
+    }
+
+    Ok(None)
+}
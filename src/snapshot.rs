@@ -0,0 +1,57 @@
+/// Copy of a process memory region taken at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySnapshot {
+    address: usize,
+    bytes: Vec<u8>,
+}
+
+impl MemorySnapshot {
+    /// Creates snapshot of `bytes` that were read from `address`.
+    pub fn new(address: usize, bytes: Vec<u8>) -> Self {
+        MemorySnapshot { address, bytes }
+    }
+
+    /// Address the snapshot starts at.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Captured bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Compares with a newer snapshot and returns `(address, old, new)` for every changed byte.
+    ///
+    /// Only the address range covered by both snapshots is compared.
+    pub fn diff(&self, other: &MemorySnapshot) -> Vec<(usize, u8, u8)> {
+        let start = self.address.max(other.address);
+        let end = self.end().min(other.end());
+
+        if start >= end {
+            return Vec::new();
+        }
+
+        let old = &self.bytes[start - self.address..end - self.address];
+        let new = &other.bytes[start - other.address..end - other.address];
+
+        old.iter()
+            .zip(new)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(index, (&old, &new))| (start + index, old, new))
+            .collect()
+    }
+
+    fn end(&self) -> usize {
+        self.address.saturating_add(self.bytes.len())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::MemoryReadError;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Reads a process has made since it was opened or the stats were reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ReadStats {
+    /// Read calls made to the OS.
+    pub reads: u64,
+    /// Bytes successfully read.
+    pub bytes: u64,
+    /// Reads that failed.
+    pub failures: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ReadCounters {
+    reads: AtomicU64,
+    bytes: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl ReadCounters {
+    pub(crate) fn record(&self, result: &Result<usize, MemoryReadError>) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+
+        match result {
+            Ok(read) => self.bytes.fetch_add(*read as u64, Ordering::Relaxed),
+            Err(_) => self.failures.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn stats(&self) -> ReadStats {
+        ReadStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.reads.store(0, Ordering::Relaxed);
+        self.bytes.store(0, Ordering::Relaxed);
+        self.failures.store(0, Ordering::Relaxed);
+    }
+}
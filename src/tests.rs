@@ -0,0 +1,746 @@
+use crate::mock::MockProcess;
+use crate::scan::SCAN_CHUNK_SIZE;
+use crate::{
+    Endianness, FieldKind, FieldSpec, FieldValue, MemoryReadError, MemoryRegion, Module, Pod,
+    Process, StringEncoding,
+};
+use std::cell::Cell;
+
+/// Base of the memory the tests map, away from null.
+const BASE: usize = 0x1000;
+
+fn process(bytes: &[u8]) -> MockProcess {
+    MockProcess::new().with_region(BASE, bytes.to_vec())
+}
+
+/// Counts the reads made through `read_bytes`, each of which is a syscall for a live process.
+struct CountingProcess {
+    process: MockProcess,
+    reads: Cell<usize>,
+}
+
+impl CountingProcess {
+    fn new(process: MockProcess) -> Self {
+        CountingProcess {
+            process,
+            reads: Cell::new(0),
+        }
+    }
+}
+
+impl Process for CountingProcess {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        self.process.base_address(module_name)
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        self.reads.set(self.reads.get() + 1);
+        self.process.read_bytes(address, buffer)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        self.process.memory_regions()
+    }
+
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        self.process.modules()
+    }
+}
+
+#[test]
+fn decodes_little_endian_numbers() {
+    let process = process(&[0x78, 0x56, 0x34, 0x12, 0xfe, 0xff, 0x00, 0x00, 0x80, 0x3f]);
+
+    assert_eq!(process.read_u8(BASE).unwrap(), 0x78);
+    assert_eq!(process.read_u16(BASE).unwrap(), 0x5678);
+    assert_eq!(process.read_u32(BASE).unwrap(), 0x1234_5678);
+    assert_eq!(process.read_i16(BASE + 4).unwrap(), -2);
+    assert_eq!(process.read_i32(BASE + 4).unwrap(), 0xfffe);
+    assert_eq!(process.read_u64(BASE).unwrap(), 0x0000_fffe_1234_5678);
+    assert_eq!(process.read_f32(BASE + 6).unwrap(), 1.0);
+}
+
+#[test]
+fn decodes_big_endian_numbers() {
+    let process =
+        process(&[0x12, 0x34, 0x56, 0x78, 0x3f, 0x80, 0x00, 0x00]).with_endianness(Endianness::Big);
+
+    assert_eq!(process.read_u16(BASE).unwrap(), 0x1234);
+    assert_eq!(process.read_u32(BASE).unwrap(), 0x1234_5678);
+    assert_eq!(process.read_f32(BASE + 4).unwrap(), 1.0);
+    assert_eq!(
+        process
+            .read_u32_with_order(BASE, Endianness::Little)
+            .unwrap(),
+        0x7856_3412
+    );
+}
+
+#[test]
+fn reads_string_until_null() {
+    let process = process(b"hello\0world\0");
+
+    assert_eq!(process.read_string(BASE).unwrap(), "hello");
+    assert_eq!(process.read_string(BASE + 6).unwrap(), "world");
+}
+
+#[test]
+fn caps_string_length() {
+    let process = process(&[b'a'; 8192]);
+
+    assert_eq!(process.read_string(BASE).unwrap().len(), 4096);
+    assert_eq!(
+        process.read_string_limited(BASE, 10).unwrap(),
+        "a".repeat(10)
+    );
+}
+
+#[test]
+fn fails_string_running_into_unmapped_memory() {
+    let process = process(b"abc");
+
+    assert!(matches!(
+        process.read_string(BASE),
+        Err(MemoryReadError::InaccessibleMemoryAddress { .. })
+    ));
+    assert_eq!(process.read_string_until_unreadable(BASE).unwrap(), "abc");
+}
+
+#[test]
+fn handles_invalid_utf8() {
+    let process = process(b"a\xffb\0");
+
+    assert_eq!(process.read_string(BASE).unwrap(), "a\u{fffd}b");
+    assert!(matches!(
+        process.read_string_strict(BASE),
+        Err(MemoryReadError::InvalidUtf8 { bytes }) if bytes == b"a\xffb"
+    ));
+}
+
+#[test]
+fn follows_pointer_chain() {
+    let mut bytes = vec![0u8; 0x30];
+    bytes[..8].copy_from_slice(&(BASE as u64 + 0x10).to_le_bytes());
+    bytes[0x18..0x20].copy_from_slice(&(BASE as u64 + 0x20).to_le_bytes());
+    bytes[0x24..0x28].copy_from_slice(&42u32.to_le_bytes());
+    let process = process(&bytes).with_pointer_size(8);
+
+    let address = process.read_pointer_chain(BASE, &[0x8, 0x4]).unwrap();
+
+    assert_eq!(address, BASE + 0x24);
+    assert_eq!(process.read_u32(address).unwrap(), 42);
+}
+
+#[test]
+fn follows_pointer_chain_of_32_bit_target() {
+    let mut bytes = vec![0u8; 0x10];
+    bytes[..4].copy_from_slice(&(BASE as u32 + 0x8).to_le_bytes());
+    let process = process(&bytes).with_pointer_size(4);
+
+    assert_eq!(
+        process.read_pointer_chain(BASE, &[0x4]).unwrap(),
+        BASE + 0xc
+    );
+}
+
+#[test]
+fn fails_pointer_chain_on_null_pointer() {
+    let process = process(&[0u8; 0x10]).with_pointer_size(8);
+
+    assert!(matches!(
+        process.read_pointer_chain(BASE, &[0x8, 0x4]),
+        Err(MemoryReadError::NullPointer { address: BASE })
+    ));
+}
+
+#[test]
+fn reads_across_adjacent_regions() {
+    let process = process(&[1, 2]).with_region(BASE + 2, vec![3, 4]);
+
+    assert_eq!(process.read_u32(BASE).unwrap(), 0x0403_0201);
+}
+
+#[test]
+fn reads_part_before_unmapped_gap() {
+    let process = process(&[1, 2]).with_region(BASE + 4, vec![5, 6]);
+    let mut buffer = [0u8; 6];
+
+    assert_eq!(process.read_bytes_partial(BASE, &mut buffer).unwrap(), 2);
+    assert_eq!(buffer[..2], [1, 2]);
+    assert!(matches!(
+        process.read_bytes(BASE, &mut buffer),
+        Err(MemoryReadError::LessBytesRead {
+            expected: 6,
+            actual: 2
+        })
+    ));
+    assert!(matches!(
+        process.read_u8(BASE + 2),
+        Err(MemoryReadError::InaccessibleMemoryAddress { .. })
+    ));
+}
+
+#[test]
+fn resolves_module_base() {
+    let process = process(&[0; 4])
+        .with_module("game.exe", BASE, 4)
+        .with_module("engine.dll", BASE + 0x1000, 4);
+
+    assert_eq!(process.base_address("engine.dll"), Some(BASE + 0x1000));
+    assert_eq!(process.main_module_base(), Some(BASE));
+    assert_eq!(process.base_address("missing.dll"), None);
+}
+
+#[test]
+fn reads_long_string_in_chunks() {
+    let mut bytes = vec![0u8; 2048];
+    bytes[..1000].fill(b'a');
+    let process = CountingProcess::new(process(&bytes));
+
+    assert_eq!(process.read_string(BASE).unwrap(), "a".repeat(1000));
+    // Four 256 byte chunks instead of a read per byte.
+    assert_eq!(process.reads.get(), 4);
+}
+
+#[test]
+fn reads_string_ending_near_region_end() {
+    let mut bytes = vec![b'a'; 299];
+    bytes.push(0);
+    let process = CountingProcess::new(process(&bytes));
+
+    assert_eq!(process.read_string(BASE).unwrap().len(), 299);
+    // The chunk straddling the region end is retried with smaller chunks.
+    assert!(process.reads.get() < 20);
+}
+
+#[test]
+fn reads_16_bit_and_signed_8_bit_numbers() {
+    let process = process(&[0x34, 0x12, 0x9c, 0xff, 0x80]);
+
+    assert_eq!(process.read_u16(BASE).unwrap(), 0x1234);
+    assert_eq!(process.read_i16(BASE + 2).unwrap(), -100);
+    assert_eq!(process.read_u16(BASE + 2).unwrap(), 0xff9c);
+    assert_eq!(process.read_i8(BASE + 4).unwrap(), -128);
+    assert_eq!(process.read_i8(BASE).unwrap(), 0x34);
+}
+
+fn utf16_bytes(string: &str) -> Vec<u8> {
+    string
+        .encode_utf16()
+        .chain(Some(0))
+        .flat_map(u16::to_le_bytes)
+        .collect()
+}
+
+#[test]
+fn reads_utf16_string() {
+    let process = process(&utf16_bytes("wide string"));
+
+    assert_eq!(process.read_string_utf16(BASE).unwrap(), "wide string");
+}
+
+#[test]
+fn reads_dotnet_string_and_rejects_negative_length() {
+    let mut bytes = vec![0u8; 8];
+    bytes.extend_from_slice(&3i32.to_le_bytes());
+    bytes.extend(utf16_bytes("abc"));
+    bytes.extend_from_slice(&(-1i32).to_le_bytes());
+    let process = process(&bytes);
+
+    assert_eq!(
+        process.read_dotnet_string_with_header(BASE, 8).unwrap(),
+        "abc"
+    );
+    assert!(matches!(
+        process.read_dotnet_string_with_header(BASE + 12, 8),
+        Err(MemoryReadError::InvalidFormat {
+            reason: "string length is negative"
+        })
+    ));
+}
+
+#[test]
+fn keeps_surrogate_pairs_across_chunks() {
+    // The pair takes units 127 and 128, on both sides of the first 256 byte chunk.
+    let string = format!("{}\u{1f600}", "a".repeat(127));
+    let process = process(&utf16_bytes(&string));
+
+    assert_eq!(process.read_string_utf16(BASE).unwrap(), string);
+}
+
+#[test]
+fn stops_utf16_string_at_odd_final_byte() {
+    let process = process(&[b'a', 0, b'b', 0, b'c']);
+
+    assert!(matches!(
+        process.read_string_utf16(BASE),
+        Err(MemoryReadError::LessBytesRead {
+            expected: 2,
+            actual: 1
+        })
+    ));
+}
+
+#[test]
+fn caps_utf16_string_length() {
+    let process = process(&utf16_bytes(&"a".repeat(3000)));
+
+    assert_eq!(process.read_string_utf16(BASE).unwrap().len(), 2048);
+}
+
+#[test]
+fn limits_string_to_max_len() {
+    let process = process(b"abcdefgh");
+
+    // No terminator within `max_len`, so the read stops there instead of running off.
+    assert_eq!(process.read_string_limited(BASE, 4).unwrap(), "abcd");
+    assert_eq!(process.read_string_limited(BASE, 8).unwrap(), "abcdefgh");
+    assert!(process.read_string_limited(BASE, 9).is_err());
+}
+
+#[test]
+fn ends_limited_string_at_terminator_before_max_len() {
+    let process = process(b"abc\0defgh");
+
+    assert_eq!(process.read_string_limited(BASE, 8).unwrap(), "abc");
+}
+
+#[test]
+fn parses_pattern() {
+    assert_eq!(
+        crate::parse_pattern("48 8B ?? ? 89"),
+        Some(vec![Some(0x48), Some(0x8b), None, None, Some(0x89)])
+    );
+    assert_eq!(crate::parse_pattern("48 8G"), None);
+}
+
+#[test]
+fn finds_pattern_at_start() {
+    let process = process(&[0x48, 0x8b, 0x05, 0x10, 0x89, 0x00]);
+    let pattern = crate::parse_pattern("48 8B ?? ?? 89").unwrap();
+
+    assert_eq!(process.find_pattern(BASE, 6, &pattern).unwrap(), Some(BASE));
+}
+
+#[test]
+fn finds_pattern_spanning_chunk_boundary() {
+    let mut bytes = vec![0u8; SCAN_CHUNK_SIZE * 2];
+    let offset = SCAN_CHUNK_SIZE - 2;
+    bytes[offset..offset + 4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+    let process = process(&bytes);
+    let pattern = crate::parse_pattern("DE ?? BE EF").unwrap();
+
+    assert_eq!(
+        process.find_pattern(BASE, bytes.len(), &pattern).unwrap(),
+        Some(BASE + offset)
+    );
+}
+
+#[test]
+fn finds_no_pattern() {
+    let process = process(&[0x48, 0x8b, 0x05, 0x10, 0x90, 0x00]);
+    let pattern = crate::parse_pattern("48 8B ?? ?? 89").unwrap();
+
+    assert_eq!(process.find_pattern(BASE, 6, &pattern).unwrap(), None);
+}
+
+#[test]
+fn finds_terminator_after_chunk_boundary() {
+    let process = process(b"abcdefghij\0");
+
+    // Chunks of 4 bytes put the terminator in the third chunk.
+    assert_eq!(
+        process.read_string_chunked(BASE, 4, 64).unwrap(),
+        "abcdefghij"
+    );
+    assert_eq!(process.read_string_chunked(BASE, 4, 6).unwrap(), "abcdef");
+    assert_eq!(
+        process.read_string_chunked(BASE, 0, 64).unwrap(),
+        "abcdefghij"
+    );
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entity {
+    hp: i32,
+    position: [f32; 3],
+}
+
+unsafe impl Pod for Entity {}
+
+#[test]
+fn round_trips_struct_through_bytes() {
+    let entity = Entity {
+        hp: -25,
+        position: [1.5, -2.0, 1024.0],
+    };
+    let bytes = crate::pod::bytes_of_slice_mut(&mut [entity]).to_vec();
+    let process = process(&bytes);
+
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(process.read::<Entity>(BASE).unwrap(), entity);
+
+    let mut read = Entity {
+        hp: 0,
+        position: [0.0; 3],
+    };
+    process.read_into(BASE, &mut read).unwrap();
+    assert_eq!(read, entity);
+    assert!(process.read::<Entity>(BASE + 1).is_err());
+}
+
+#[test]
+fn reads_f32_array_with_single_read() {
+    let values = [1.0f32, -0.5, 3.25, 100.0];
+    let bytes: Vec<u8> = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    // Odd base, so the values are unaligned in the buffer.
+    let process = CountingProcess::new(MockProcess::new().with_region(BASE + 1, bytes));
+
+    assert_eq!(process.read_array::<f32, 4>(BASE + 1).unwrap(), values);
+    assert_eq!(process.read_slice::<f32>(BASE + 1, 4).unwrap(), values);
+    assert_eq!(process.reads.get(), 2);
+    assert!(process.read_slice::<f32>(BASE + 1, 0).unwrap().is_empty());
+    assert!(process.read_slice::<f32>(BASE + 1, 5).is_err());
+}
+
+/// `mov rax, [rip + displacement]` at `BASE + 0x100`.
+fn rip_relative_instruction(displacement: i32) -> MockProcess {
+    let mut bytes = vec![0u8; 0x200];
+    bytes[0x100..0x103].copy_from_slice(&[0x48, 0x8b, 0x05]);
+    bytes[0x103..0x107].copy_from_slice(&displacement.to_le_bytes());
+
+    process(&bytes)
+}
+
+#[test]
+fn resolves_positive_rip_relative_displacement() {
+    let process = rip_relative_instruction(0x20);
+
+    assert_eq!(
+        process.resolve_rip_relative(BASE + 0x100, 3, 7).unwrap(),
+        BASE + 0x107 + 0x20
+    );
+}
+
+#[test]
+fn resolves_negative_rip_relative_displacement() {
+    let process = rip_relative_instruction(-0x50);
+
+    assert_eq!(
+        process.resolve_rip_relative(BASE + 0x100, 3, 7).unwrap(),
+        BASE + 0x107 - 0x50
+    );
+}
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+#[test]
+fn moves_process_into_thread() {
+    static VALUE: u32 = 0x1234_5678;
+    let process = crate::open_process(std::process::id()).unwrap();
+    let address = &VALUE as *const u32 as usize;
+
+    assert_send_sync(&process);
+
+    // Shared by reference between scoped threads, which needs `Sync`.
+    std::thread::scope(|scope| {
+        scope.spawn(|| assert_eq!(process.read_u32(address).unwrap(), VALUE));
+    });
+
+    // Moved into the thread, which needs `Send`.
+    let value = std::thread::spawn(move || process.read_u32(address).unwrap())
+        .join()
+        .unwrap();
+
+    assert_eq!(value, VALUE);
+}
+
+#[test]
+fn decodes_latin1_and_utf8_differently() {
+    let process = process(b"caf\xe9 \xa3\xff\0");
+
+    assert_eq!(
+        process.read_cstring(BASE, StringEncoding::Latin1).unwrap(),
+        "caf\u{e9} \u{a3}\u{ff}"
+    );
+    assert_eq!(
+        process.read_cstring(BASE, StringEncoding::Utf8).unwrap(),
+        "caf\u{fffd} \u{fffd}\u{fffd}"
+    );
+}
+
+#[test]
+fn decodes_utf16_cstring_in_both_byte_orders() {
+    let process = process(&[0x00, b'h', 0x00, b'i', 0x00, 0x00, 0x00]);
+
+    assert_eq!(
+        process.read_cstring(BASE, StringEncoding::Utf16Be).unwrap(),
+        "hi"
+    );
+    assert_eq!(
+        process
+            .read_cstring(BASE + 1, StringEncoding::Utf16Le)
+            .unwrap(),
+        "hi"
+    );
+}
+
+#[test]
+fn caps_cstring_length() {
+    let process = process(&[0xe9; 8192]);
+
+    assert_eq!(
+        process
+            .read_cstring(BASE, StringEncoding::Latin1)
+            .unwrap()
+            .chars()
+            .count(),
+        4096
+    );
+}
+
+#[test]
+fn reads_empty_buffer_anywhere() {
+    let process = crate::open_process(std::process::id()).unwrap();
+
+    assert!(process.read_bytes(0, &mut []).is_ok());
+    assert!(process.read_bytes(usize::MAX, &mut []).is_ok());
+    assert_eq!(process.read_bytes_partial(0, &mut []).unwrap(), 0);
+}
+
+#[test]
+fn rejects_read_wrapping_address_space() {
+    let process = crate::open_process(std::process::id()).unwrap();
+
+    let address = usize::MAX - 1;
+
+    assert!(matches!(
+        process.read_bytes(address, &mut [0u8; 4]),
+        Err(MemoryReadError::AddressOverflow { address: at, offset: 4 }) if at == address
+    ));
+    assert!(matches!(
+        process.read_u16(usize::MAX),
+        Err(MemoryReadError::AddressOverflow { .. })
+    ));
+}
+
+/// PE32+ image exporting `answer` at offset 0x180.
+fn pe_image() -> Vec<u8> {
+    let mut image = vec![0u8; 0x200];
+    image[..2].copy_from_slice(b"MZ");
+    image[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+    image[0x40..0x44].copy_from_slice(b"PE\0\0");
+    image[0x58..0x5a].copy_from_slice(&0x20bu16.to_le_bytes());
+
+    // Export directory entry, then the directory and its tables.
+    let fields: [(usize, u32); 9] = [
+        (0xc8, 0x100),
+        (0xcc, 0x28),
+        (0x110, 1),
+        (0x114, 1),
+        (0x118, 1),
+        (0x11c, 0x140),
+        (0x120, 0x144),
+        (0x124, 0x148),
+        (0x140, 0x180),
+    ];
+
+    for (offset, value) in fields {
+        image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    image[0x144..0x148].copy_from_slice(&0x150u32.to_le_bytes());
+    image[0x150..0x157].copy_from_slice(b"answer\0");
+    image
+}
+
+#[test]
+fn rebases_only_supplied_addresses() {
+    /// Preferred base the offsets were recorded against; the module is loaded at `BASE`.
+    const RECORDED: usize = 0x40_0000;
+    const MODULE: usize = BASE + 0x1000;
+
+    let mut bytes = vec![0u8; 0x200];
+    bytes[0x10..0x18].copy_from_slice(&(BASE as u64 + 0x100).to_le_bytes());
+    bytes[0x20..0x24].copy_from_slice(&5u32.to_le_bytes());
+    bytes[0x40..0x44].copy_from_slice(&0xdead_beefu32.to_le_bytes());
+    bytes[0x108..0x10c].copy_from_slice(&7u32.to_le_bytes());
+    let process = process(&bytes)
+        .with_pointer_size(8)
+        .with_region(MODULE, pe_image())
+        .with_module("game.exe", MODULE, 0x200);
+    let rebased = process.rebased(|address| address - RECORDED + BASE);
+
+    assert_eq!(rebased.read_u32(RECORDED + 0x20).unwrap(), 5);
+    // The pointer read at the rebased address is a runtime one and is followed as is.
+    assert_eq!(
+        rebased.read_pointer_chain(RECORDED + 0x10, &[0x8]).unwrap(),
+        BASE + 0x108
+    );
+    assert_eq!(
+        rebased.read_chain::<u32>(RECORDED + 0x10, &[0x8]).unwrap(),
+        7
+    );
+    assert_eq!(rebased.read_u32_deref(RECORDED + 0x10, 0x8).unwrap(), 7);
+
+    // Module and region queries return live addresses, which are read as they are.
+    assert_eq!(
+        rebased.exports("game.exe").unwrap(),
+        [("answer".to_string(), MODULE + 0x180)]
+    );
+    assert_eq!(
+        rebased.get_export("game.exe", "answer").unwrap(),
+        MODULE + 0x180
+    );
+    assert_eq!(
+        rebased.find_value(RECORDED, 0x200, 0xdead_beefu32).unwrap(),
+        [BASE + 0x40]
+    );
+    assert_eq!(
+        rebased.build_pointer_map(8).unwrap().len(),
+        process.build_pointer_map(8).unwrap().len()
+    );
+}
+
+#[test]
+fn reads_virtual_function() {
+    let mut bytes = vec![0u8; 0x30];
+    bytes[..8].copy_from_slice(&(BASE as u64 + 0x10).to_le_bytes());
+    bytes[0x18..0x20].copy_from_slice(&0x5000u64.to_le_bytes());
+    let process = process(&bytes).with_pointer_size(8);
+
+    assert_eq!(process.read_vfunc(BASE, 1).unwrap(), 0x5000);
+    assert!(matches!(
+        process.read_vfunc(BASE, usize::MAX / 4),
+        Err(MemoryReadError::InvalidLength { .. })
+    ));
+    assert!(matches!(
+        process.read_vfunc(BASE, usize::MAX / 8),
+        Err(MemoryReadError::AddressOverflow { .. })
+    ));
+}
+
+#[test]
+fn reads_struct_fields() {
+    let mut bytes = vec![0u8; 0x10];
+    bytes[4..8].copy_from_slice(&7u32.to_le_bytes());
+    bytes[8..12].copy_from_slice(&1.5f32.to_le_bytes());
+    let process = process(&bytes);
+    let fields = [
+        FieldSpec::new(8, FieldKind::F32),
+        FieldSpec::new(4, FieldKind::U32),
+    ];
+
+    assert_eq!(
+        process.read_struct_fields(BASE, &fields).unwrap(),
+        [FieldValue::F32(1.5), FieldValue::U32(7)]
+    );
+    assert!(matches!(
+        process.read_struct_fields(BASE, &[FieldSpec::new(usize::MAX - 1, FieldKind::U32)]),
+        Err(MemoryReadError::AddressOverflow { .. })
+    ));
+}
+
+#[test]
+fn fails_utf32_string_wrapping_address_space() {
+    let process = MockProcess::new().with_region(usize::MAX - 7, b"a\0\0\0b\0\0\0".to_vec());
+
+    assert!(matches!(
+        process.read_utf32_string(usize::MAX - 7),
+        Err(MemoryReadError::AddressOverflow { .. })
+    ));
+}
+
+#[test]
+fn reads_packed_strings_in_chunks() {
+    let mut bytes = b"first\0\0third\0".to_vec();
+    bytes.resize(0x1000, 0);
+    let process = CountingProcess::new(process(&bytes));
+
+    assert_eq!(
+        process.read_packed_strings(BASE, 3).unwrap(),
+        ["first", "", "third"]
+    );
+    assert_eq!(process.reads.get(), 3);
+}
+
+#[test]
+fn rejects_packed_string_longer_than_cap() {
+    let mut bytes = vec![b'a'; 5000];
+    bytes.push(0);
+    let process = process(&bytes);
+
+    assert!(matches!(
+        process.read_packed_strings(BASE, 1),
+        Err(MemoryReadError::InvalidLength { max: 4096, .. })
+    ));
+}
+
+fn utf32_bytes(string: &str) -> Vec<u8> {
+    string
+        .chars()
+        .flat_map(|ch| (ch as u32).to_le_bytes())
+        .collect()
+}
+
+#[test]
+fn reads_utf32_string_in_chunks() {
+    let mut bytes = utf32_bytes(&"é".repeat(100));
+    bytes.resize(0x1000, 0);
+    let process = CountingProcess::new(process(&bytes));
+
+    assert_eq!(process.read_utf32_string(BASE).unwrap(), "é".repeat(100));
+    assert_eq!(process.reads.get(), 2);
+}
+
+#[test]
+fn caps_utf32_string_length() {
+    let mut bytes = utf32_bytes(&"a".repeat(2000));
+    bytes.resize(0x3000, 0);
+    let process = process(&bytes);
+
+    assert_eq!(process.read_utf32_string(BASE).unwrap(), "a".repeat(1024));
+}
+
+#[test]
+fn rejects_zero_pointer_map_alignment() {
+    let process = process(&[0u8; 0x10]).with_pointer_size(8);
+
+    assert!(matches!(
+        process.build_pointer_map(0),
+        Err(MemoryReadError::InvalidLength { length: 0, .. })
+    ));
+    assert!(process.build_pointer_map(8).is_ok());
+}
+
+#[test]
+fn rejects_watched_values_bigger_than_max_read_size() {
+    let process = process(&[0u8; 0x10]);
+
+    assert!(process
+        .watch(&[BASE, BASE + 8], 8)
+        .poll()
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        process.watch(&[BASE, BASE + 8], usize::MAX).poll(),
+        Err(MemoryReadError::ReadTooLarge { .. })
+    ));
+}
+
+#[test]
+fn dumps_unreadable_last_page_as_zeros() {
+    let process = process(&[1u8; 0x10]);
+    let address = usize::MAX - 0xfff;
+    let mut output = Vec::new();
+
+    assert_eq!(
+        process.dump_region(address, 0xfff, &mut output).unwrap(),
+        0xfff
+    );
+    assert_eq!(output, vec![0u8; 0xfff]);
+}
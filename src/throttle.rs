@@ -0,0 +1,58 @@
+use std::sync::{Mutex, PoisonError};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Spaces reads out so that at most `reads_per_second` are made.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    reads_per_second: u32,
+    interval: Duration,
+    next_read: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Limiter for `reads_per_second`, or `None` when it is 0, which means no limit.
+    pub(crate) fn new(reads_per_second: u32) -> Option<Self> {
+        if reads_per_second == 0 {
+            return None;
+        }
+
+        Some(RateLimiter {
+            reads_per_second,
+            interval: Duration::from_secs(1) / reads_per_second,
+            next_read: Mutex::new(Instant::now()),
+        })
+    }
+
+    pub(crate) fn reads_per_second(&self) -> u32 {
+        self.reads_per_second
+    }
+
+    /// Sleeps until the interval since the previous read has passed.
+    ///
+    /// The lock is held while sleeping, so reads from several threads are spaced out as well.
+    pub(crate) fn wait(&self) {
+        let mut next_read = self
+            .next_read
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+
+        if *next_read > now {
+            sleep(*next_read - now);
+        }
+
+        *next_read = (*next_read).max(now) + self.interval;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_reads_per_second_means_no_limit() {
+        assert!(RateLimiter::new(0).is_none());
+        assert_eq!(RateLimiter::new(10).unwrap().reads_per_second(), 10);
+    }
+}
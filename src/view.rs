@@ -0,0 +1,86 @@
+use crate::Endianness;
+use std::convert::TryInto;
+
+macro_rules! define_number_at (
+    ($type: ty, $name: ident, $size: expr) => (
+        #[doc = concat!("Decodes `", stringify!($type), "` at `offset` into the view.")]
+        pub fn $name(&self, offset: usize) -> Option<$type> {
+            let bytes = self.slice(offset, $size)?.try_into().ok()?;
+
+            Some(match self.endianness {
+                Endianness::Little => <$type>::from_le_bytes(bytes),
+                Endianness::Big => <$type>::from_be_bytes(bytes),
+            })
+        }
+    );
+);
+
+/// Bytes of process memory copied with a single read, indexed by offsets from its start.
+///
+/// Created by `Process::map_region`. Lookups only use the local copy; those that don't fit in
+/// the view return `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionView {
+    address: usize,
+    bytes: Vec<u8>,
+    endianness: Endianness,
+}
+
+impl RegionView {
+    /// Creates view of `bytes` read from `address`, decoding numbers in `endianness`.
+    pub fn new(address: usize, bytes: Vec<u8>, endianness: Endianness) -> Self {
+        RegionView {
+            address,
+            bytes,
+            endianness,
+        }
+    }
+
+    /// Address the view starts at.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Copied bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// `len` bytes starting at `offset`.
+    pub fn slice(&self, offset: usize, len: usize) -> Option<&[u8]> {
+        self.bytes.get(offset..offset.checked_add(len)?)
+    }
+
+    pub fn u8_at(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(offset).copied()
+    }
+
+    define_number_at!(u16, u16_at, 2);
+    define_number_at!(u32, u32_at, 4);
+    define_number_at!(u64, u64_at, 8);
+    define_number_at!(i32, i32_at, 4);
+    define_number_at!(i64, i64_at, 8);
+    define_number_at!(f32, f32_at, 4);
+    define_number_at!(f64, f64_at, 8);
+
+    /// String at `offset` up to null char or the end of the view.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    pub fn string_at(&self, offset: usize) -> Option<String> {
+        let bytes = self.bytes.get(offset..)?;
+        let len = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(bytes.len());
+
+        Some(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+}
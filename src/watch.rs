@@ -0,0 +1,114 @@
+use crate::{check_read_size, offset_address, MemoryReadError, Process};
+use std::sync::{Mutex, PoisonError};
+
+/// Largest span of watched values fetched by a single read.
+const WATCH_SPAN_SIZE: usize = 4096;
+
+/// Watched value whose bytes differ from the previous poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub address: usize,
+    pub old: Vec<u8>,
+    pub new: Vec<u8>,
+}
+
+/// Set of same-sized values polled for changes, created by `Process::watch`.
+///
+/// Values close to each other are fetched with a single read spanning them, so the memory
+/// between them must be readable as well.
+pub struct Watcher<'a, P: ?Sized> {
+    process: &'a P,
+    addresses: Vec<usize>,
+    size: usize,
+    /// Bytes of every address read by the previous poll, in the order of `addresses`.
+    values: Mutex<Option<Vec<u8>>>,
+}
+
+impl<'a, P: Process + ?Sized> Watcher<'a, P> {
+    pub(crate) fn new(process: &'a P, addresses: &[usize], size: usize) -> Self {
+        let mut addresses = addresses.to_vec();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        Watcher {
+            process,
+            addresses,
+            size,
+            values: Mutex::new(None),
+        }
+    }
+
+    /// Watched addresses in ascending order.
+    pub fn addresses(&self) -> &[usize] {
+        &self.addresses
+    }
+
+    /// Reads all watched values and returns those that changed since the previous poll.
+    ///
+    /// The first poll only stores the values and returns no changes. Previous values are
+    /// kept when a read fails. Values bigger than `max_read_size` fail with `ReadTooLarge`.
+    pub fn poll(&self) -> Result<Vec<Change>, MemoryReadError> {
+        let current = self.read_values()?;
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let changes = match values.as_ref() {
+            Some(previous) => self
+                .addresses
+                .iter()
+                .enumerate()
+                .filter_map(|(index, &address)| {
+                    let range = index * self.size..(index + 1) * self.size;
+                    let (old, new) = (&previous[range.clone()], &current[range]);
+
+                    (old != new).then(|| Change {
+                        address,
+                        old: old.to_vec(),
+                        new: new.to_vec(),
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        *values = Some(current);
+
+        Ok(changes)
+    }
+
+    fn read_values(&self) -> Result<Vec<u8>, MemoryReadError> {
+        check_read_size(self.size, self.process.max_read_size())?;
+
+        let total = self.addresses.len().checked_mul(self.size).ok_or_else(|| {
+            MemoryReadError::InvalidLength {
+                length: self.addresses.len(),
+                max: usize::MAX / self.size,
+            }
+        })?;
+        let mut values = Vec::with_capacity(total);
+        let mut remaining = &self.addresses[..];
+
+        while let Some(&start) = remaining.first() {
+            let count = remaining
+                .iter()
+                .take_while(|&&address| {
+                    (address - start)
+                        .checked_add(self.size)
+                        .is_some_and(|end| end <= WATCH_SPAN_SIZE)
+                })
+                .count()
+                .max(1);
+
+            let end = offset_address(remaining[count - 1], self.size)?;
+            let span = self.process.read_bytes_vec(start, end - start)?;
+
+            for &address in &remaining[..count] {
+                let offset = address - start;
+                values.extend_from_slice(&span[offset..offset + self.size]);
+            }
+
+            remaining = &remaining[count..];
+        }
+
+        Ok(values)
+    }
+}
@@ -1,82 +1,1062 @@
-use crate::{MemoryReadError, Process};
+use crate::region::RegionCache;
+#[cfg(feature = "metrics")]
+use crate::stats::ReadCounters;
+use crate::throttle::RateLimiter;
+#[cfg(feature = "metrics")]
+use crate::ReadStats;
+use crate::{
+    as_uninit_mut, assume_init_mut, check_pointer_size, check_read_size, is_aligned_pointer,
+    offset_address, pe, Architecture, CachePolicy, Capabilities, Capability, Endianness,
+    LoadedModule, MemoryReadError, MemoryRegion, MemoryUsage, MemoryWriteError, Module,
+    OpenProcessError, Process, ProcessEntry, ProcessStatus, ProcessWriter, RegionKind,
+    DEFAULT_MAX_READ_SIZE,
+};
+use ntapi::ntpsapi::{
+    NtQueryInformationProcess, NtQueryInformationThread, ProcessBasicInformation,
+    ThreadBasicInformation, ThreadQuerySetWin32StartAddress, PROCESS_BASIC_INFORMATION,
+    THREAD_BASIC_INFORMATION,
+};
+use ntapi::ntrtl::RtlNtStatusToDosError;
 use std::ffi::OsString;
+use std::hash::{Hash, Hasher};
+use std::io::Error as IoError;
 use std::mem::{size_of, size_of_val, MaybeUninit};
 use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
 use std::ptr;
+use std::slice;
+use std::sync::OnceLock;
 use winapi::ctypes::c_void;
-use winapi::shared::minwindef::{DWORD, HMODULE, MAX_PATH, TRUE};
-use winapi::um::handleapi::CloseHandle;
-use winapi::um::memoryapi::ReadProcessMemory;
-use winapi::um::processthreadsapi::OpenProcess;
-use winapi::um::psapi::{EnumProcessModules, GetModuleBaseNameA};
-use winapi::um::tlhelp32::PROCESSENTRY32W;
+#[cfg(feature = "user32")]
+use winapi::shared::minwindef::LPARAM;
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, HMODULE, MAX_PATH, TRUE};
+use winapi::shared::ntdef::NTSTATUS;
+#[cfg(feature = "user32")]
+use winapi::shared::windef::HWND;
+use winapi::shared::winerror::{ERROR_INVALID_PARAMETER, ERROR_PARTIAL_COPY};
+use winapi::um::handleapi::{CloseHandle, DuplicateHandle, INVALID_HANDLE_VALUE};
+use winapi::um::libloaderapi::{GetModuleHandleW, GetProcAddress};
+use winapi::um::memoryapi::{ReadProcessMemory, VirtualQueryEx, WriteProcessMemory};
+use winapi::um::minwinbase::STILL_ACTIVE;
+use winapi::um::processthreadsapi::{
+    GetCurrentProcess, GetExitCodeProcess, OpenProcess, OpenThread,
+};
+use winapi::um::psapi::{
+    EnumProcessModules, EnumProcessModulesEx, GetMappedFileNameW, GetModuleBaseNameW,
+    GetModuleFileNameExW, GetModuleInformation, GetProcessMemoryInfo, LIST_MODULES_ALL, MODULEINFO,
+    PROCESS_MEMORY_COUNTERS, PROCESS_MEMORY_COUNTERS_EX,
+};
+use winapi::um::shellapi::CommandLineToArgvW;
+use winapi::um::stringapiset::CompareStringOrdinal;
+use winapi::um::sysinfoapi::{GetNativeSystemInfo, GetSystemInfo, SYSTEM_INFO};
 use winapi::um::tlhelp32::{
-    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, TH32CS_SNAPPROCESS,
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, Thread32First, Thread32Next,
+    PROCESSENTRY32W, TH32CS_SNAPPROCESS, TH32CS_SNAPTHREAD, THREADENTRY32,
 };
-use winapi::um::winnt::{PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+use winapi::um::winbase::{LocalFree, QueryFullProcessImageNameW};
+use winapi::um::winnt::{
+    DUPLICATE_SAME_ACCESS, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM,
+    IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_ARMNT, IMAGE_FILE_MACHINE_I386,
+    IMAGE_FILE_MACHINE_UNKNOWN, MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_MAPPED,
+    PAGE_EXECUTE, PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD,
+    PAGE_NOACCESS, PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY, PROCESSOR_ARCHITECTURE_AMD64,
+    PROCESSOR_ARCHITECTURE_ARM, PROCESSOR_ARCHITECTURE_INTEL, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_OPERATION, PROCESS_VM_READ, PROCESS_VM_WRITE,
+    THREAD_QUERY_INFORMATION, THREAD_QUERY_LIMITED_INFORMATION,
+};
+#[cfg(feature = "user32")]
+use winapi::um::winuser::{
+    EnumWindows, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+};
+use winapi::um::wow64apiset::IsWow64Process;
 
 /// Opens process with specified id.
 ///
 /// If the process is not found or could not be opened `None` will be returned.
 pub fn open_process(pid: u32) -> Option<WindowsProcess> {
-    let handle = unsafe { OpenProcess(PROCESS_VM_READ | PROCESS_QUERY_INFORMATION, 0, pid) };
+    open_process_with_access(pid, PROCESS_VM_READ | PROCESS_QUERY_INFORMATION)
+}
+
+/// Opens process with specified id, requesting `access` rights for the handle.
+///
+/// Reading needs `PROCESS_VM_READ | PROCESS_QUERY_INFORMATION`; writing additionally needs
+/// `PROCESS_VM_WRITE | PROCESS_VM_OPERATION`.
+pub fn open_process_with_access(pid: u32, access: DWORD) -> Option<WindowsProcess> {
+    open_process_with_access_checked(pid, access).ok()
+}
+
+/// Opens process with specified id for reading, telling why it could not be opened.
+///
+/// Elevated and protected processes fail with `AccessDenied` unless this process runs elevated.
+pub fn open_process_checked(pid: u32) -> Result<WindowsProcess, OpenProcessError> {
+    open_process_with_access_checked(pid, PROCESS_VM_READ | PROCESS_QUERY_INFORMATION)
+}
+
+/// Same as `open_process_with_access`, telling why the process could not be opened.
+pub fn open_process_with_access_checked(
+    pid: u32,
+    access: DWORD,
+) -> Result<WindowsProcess, OpenProcessError> {
+    let handle = unsafe { OpenProcess(access, 0, pid) };
 
     if handle.is_null() {
-        return None;
+        let error = IoError::last_os_error();
+        trace_event!(debug, pid, %error, "failed to open process");
+
+        // Ids that belong to no process are rejected as an invalid parameter.
+        if error.raw_os_error() == Some(ERROR_INVALID_PARAMETER as i32) {
+            return Err(OpenProcessError::NotFound { pid });
+        }
+
+        return Err(OpenProcessError::from_io_error(pid, error));
     }
 
-    Some(WindowsProcess { pid, handle })
+    trace_event!(debug, pid, "opened process");
+
+    Ok(WindowsProcess {
+        pid,
+        handle,
+        access,
+        max_read_size: DEFAULT_MAX_READ_SIZE,
+        endianness: Endianness::default(),
+        regions: RegionCache::default(),
+        rate_limiter: None,
+        pointer_size: OnceLock::new(),
+        #[cfg(feature = "metrics")]
+        read_counters: ReadCounters::default(),
+    })
 }
 
-/// Finds all processes with matching name.
-pub fn find_by_name(name: &str) -> Vec<WindowsProcess> {
+/// Lists ids of all running processes.
+pub fn all_pids() -> Result<Vec<u32>, IoError> {
+    Ok(process_entries()?
+        .into_iter()
+        .map(|entry| entry.pid)
+        .collect())
+}
+
+/// Calls `visit` with every process of a single toolhelp snapshot.
+fn visit_toolhelp_processes<F: FnMut(&PROCESSENTRY32W)>(mut visit: F) -> Result<(), IoError> {
     let handle = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
-    let mut processes = Vec::new();
 
-    if handle.is_null() {
-        return processes;
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(IoError::last_os_error());
     }
 
-    let mut maybe_entry = MaybeUninit::<PROCESSENTRY32W>::uninit();
+    let mut entry = MaybeUninit::<PROCESSENTRY32W>::zeroed();
 
     unsafe {
-        ptr::write(
-            &mut (*maybe_entry.as_mut_ptr()).dwSize,
-            size_of::<PROCESSENTRY32W>() as u32,
-        );
+        (*entry.as_mut_ptr()).dwSize = size_of::<PROCESSENTRY32W>() as u32;
     }
 
-    if unsafe { Process32FirstW(handle, maybe_entry.as_mut_ptr()) } == TRUE {
-        while unsafe { Process32NextW(handle, maybe_entry.as_mut_ptr()) } == TRUE {
-            let entry = unsafe { maybe_entry.assume_init() };
+    let mut result = unsafe { Process32FirstW(handle, entry.as_mut_ptr()) };
 
-            let process_name_full = &entry.szExeFile;
-            let process_name_length = process_name_full.iter().take_while(|&&c| c != 0).count();
-            let process_name = &OsString::from_wide(&process_name_full[..process_name_length]);
+    while result == TRUE {
+        visit(unsafe { entry.assume_init_ref() });
+        result = unsafe { Process32NextW(handle, entry.as_mut_ptr()) };
+    }
 
-            if process_name != name {
-                continue;
-            }
+    unsafe {
+        CloseHandle(handle);
+    }
 
-            open_process(entry.th32ProcessID).map(|process| processes.push(process));
-        }
+    Ok(())
+}
+
+fn toolhelp_name(process: &PROCESSENTRY32W) -> String {
+    let name_length = process.szExeFile.iter().take_while(|&&c| c != 0).count();
+
+    String::from_utf16_lossy(&process.szExeFile[..name_length])
+}
+
+/// Lists running processes from a single toolhelp snapshot.
+pub(crate) fn process_entries() -> Result<Vec<ProcessEntry>, IoError> {
+    let mut entries = Vec::new();
+
+    visit_toolhelp_processes(|process| {
+        entries.push(ProcessEntry {
+            pid: process.th32ProcessID,
+            name: toolhelp_name(process),
+        })
+    })?;
+
+    Ok(entries)
+}
+
+/// Finds processes whose executable path, as reported by `QueryFullProcessImageNameW`,
+/// contains `path`.
+///
+/// A full path selects one install; a part such as a directory selects all under it. Paths are
+/// compared ignoring ASCII case.
+pub fn find_by_path(path: &str) -> Result<Vec<WindowsProcess>, IoError> {
+    let path = path.to_ascii_lowercase();
+
+    Ok(process_entries()?
+        .into_iter()
+        .filter(|entry| {
+            image_path(entry.pid)
+                .is_some_and(|image| image.to_string_lossy().to_ascii_lowercase().contains(&path))
+        })
+        .filter_map(|entry| open_process(entry.pid))
+        .collect())
+}
+
+/// Full path of the executable of process `pid`.
+fn image_path(pid: u32) -> Option<PathBuf> {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+
+    if handle.is_null() {
+        return None;
     }
 
+    let image_path = query_image_path(handle);
+
     unsafe {
         CloseHandle(handle);
     }
 
-    processes
+    image_path
+}
+
+/// Full path of the executable of the process `handle` was opened for.
+fn query_image_path(handle: *mut c_void) -> Option<PathBuf> {
+    let mut buffer = vec![0u16; MAX_LONG_PATH];
+    let mut len = buffer.len() as DWORD;
+    let result = unsafe { QueryFullProcessImageNameW(handle, 0, buffer.as_mut_ptr(), &mut len) };
+
+    if result != TRUE {
+        return None;
+    }
+
+    Some(PathBuf::from(OsString::from_wide(&buffer[..len as usize])))
+}
+
+/// Window title searched for by `find_by_window_title` and the pid of its owner.
+#[cfg(feature = "user32")]
+struct WindowSearch {
+    title: Vec<u16>,
+    pid: Option<u32>,
+}
+
+#[cfg(feature = "user32")]
+unsafe extern "system" fn match_window_title(window: HWND, search: LPARAM) -> BOOL {
+    let search = &mut *(search as *mut WindowSearch);
+    // Sized for the whole title, as a buffer of the searched length would truncate longer
+    // titles down to a match.
+    let mut title = vec![0u16; GetWindowTextLengthW(window).max(0) as usize + 1];
+    let length = GetWindowTextW(window, title.as_mut_ptr(), title.len() as i32);
+
+    if title[..length as usize] != *search.title {
+        return TRUE;
+    }
+
+    let mut pid = 0;
+    GetWindowThreadProcessId(window, &mut pid);
+    search.pid = Some(pid);
+
+    FALSE
+}
+
+/// Opens process owning the top-level window with exactly matching title.
+#[cfg(feature = "user32")]
+pub fn find_by_window_title(title: &str) -> Option<WindowsProcess> {
+    let mut search = WindowSearch {
+        title: title.encode_utf16().collect(),
+        pid: None,
+    };
+
+    unsafe {
+        EnumWindows(
+            Some(match_window_title),
+            &mut search as *mut WindowSearch as LPARAM,
+        );
+    }
+
+    open_process(search.pid?)
+}
+
+/// Finds all processes with matching name.
+pub fn find_by_name(name: &str) -> Vec<WindowsProcess> {
+    process_entries()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.name == name)
+        .filter_map(|entry| open_process(entry.pid))
+        .collect()
+}
+
+/// Builds region from `VirtualQueryEx` protection flags.
+fn region_from_protection(
+    base: usize,
+    size: usize,
+    protection: DWORD,
+    kind: RegionKind,
+) -> MemoryRegion {
+    let accessible = protection & (PAGE_GUARD | PAGE_NOACCESS) == 0;
+    let has = |flags: DWORD| accessible && protection & flags != 0;
+
+    MemoryRegion {
+        base,
+        size,
+        readable: has(PAGE_READONLY
+            | PAGE_READWRITE
+            | PAGE_WRITECOPY
+            | PAGE_EXECUTE_READ
+            | PAGE_EXECUTE_READWRITE
+            | PAGE_EXECUTE_WRITECOPY),
+        writable: has(PAGE_READWRITE
+            | PAGE_WRITECOPY
+            | PAGE_EXECUTE_READWRITE
+            | PAGE_EXECUTE_WRITECOPY),
+        executable: has(PAGE_EXECUTE
+            | PAGE_EXECUTE_READ
+            | PAGE_EXECUTE_READWRITE
+            | PAGE_EXECUTE_WRITECOPY),
+        kind,
+    }
+}
+
+/// Converts failed `NTSTATUS` into the matching Win32 error.
+fn nt_status_error(status: NTSTATUS) -> MemoryReadError {
+    let code = unsafe { RtlNtStatusToDosError(status) };
+
+    IoError::from_raw_os_error(code as i32).into()
+}
+
+/// Most loader list entries `module_path_from_ldr` follows, in case the list is corrupted.
+const MAX_LOADER_ENTRIES: usize = 4096;
+
+/// TLS slots stored directly in the TEB.
+const TLS_MINIMUM_AVAILABLE: usize = 64;
+
+/// TLS slots of the separately allocated expansion array.
+const TLS_EXPANSION_SLOTS: usize = 1024;
+
+/// Distance from the native TEB of a WOW64 thread to its 32-bit TEB.
+const WOW64_TEB_OFFSET: usize = 0x2000;
+
+/// Picks field offset of environment block structures for this process' pointer width.
+fn native_offset(offset_32: usize, offset_64: usize) -> usize {
+    if size_of::<usize>() == 8 {
+        offset_64
+    } else {
+        offset_32
+    }
+}
+
+/// Reads TLS slot `slot_index` of the thread whose native TEB is at `teb`, from its 32-bit TEB
+/// with `wow64`.
+fn read_tls_slot_at<P: Process + ?Sized>(
+    process: &P,
+    teb: usize,
+    slot_index: usize,
+    wow64: bool,
+) -> Result<usize, MemoryReadError> {
+    let (teb, slots, expansion, slot_size) = if wow64 {
+        (offset_address(teb, WOW64_TEB_OFFSET)?, 0xe10, 0xf94, 4)
+    } else {
+        (
+            teb,
+            native_offset(0xe10, 0x1480),
+            native_offset(0xf94, 0x1780),
+            size_of::<usize>(),
+        )
+    };
+    let read_slot = |address| match slot_size {
+        8 => process.read_u64(address).map(|value| value as usize),
+        _ => process.read_u32(address).map(|value| value as usize),
+    };
+
+    if slot_index < TLS_MINIMUM_AVAILABLE {
+        return read_slot(offset_address(teb, slots + slot_index * slot_size)?);
+    }
+
+    match read_slot(offset_address(teb, expansion)?)? {
+        0 => Ok(0),
+        expansion_slots => read_slot(offset_address(
+            expansion_slots,
+            (slot_index - TLS_MINIMUM_AVAILABLE) * slot_size,
+        )?),
+    }
+}
+
+/// Signature of `IsWow64Process2`, which Windows versions before 10 1511 lack.
+type IsWow64Process2 = unsafe extern "system" fn(*mut c_void, *mut u16, *mut u16) -> BOOL;
+
+/// Looks `IsWow64Process2` up in kernel32 once, so the crate still loads where it is missing.
+fn is_wow64_process2() -> Option<IsWow64Process2> {
+    static FUNCTION: OnceLock<Option<IsWow64Process2>> = OnceLock::new();
+
+    *FUNCTION.get_or_init(|| {
+        let kernel32: Vec<u16> = "kernel32.dll\0".encode_utf16().collect();
+        let module = unsafe { GetModuleHandleW(kernel32.as_ptr()) };
+
+        if module.is_null() {
+            return None;
+        }
+
+        let function = unsafe { GetProcAddress(module, b"IsWow64Process2\0".as_ptr() as *const _) };
+
+        (!function.is_null())
+            .then(|| unsafe { std::mem::transmute::<_, IsWow64Process2>(function) })
+    })
+}
+
+/// `CompareStringOrdinal` result for equal strings.
+const CSTR_EQUAL: i32 = 2;
+
+/// Compares UTF-16 strings ordinally, ignoring case.
+fn equals_ignore_case(left: &[u16], right: &[u16]) -> bool {
+    let result = unsafe {
+        CompareStringOrdinal(
+            left.as_ptr(),
+            left.len() as i32,
+            right.as_ptr(),
+            right.len() as i32,
+            TRUE,
+        )
+    };
+
+    result == CSTR_EQUAL
 }
 
+/// Characters of the longest path, which long path names can reach.
+const MAX_LONG_PATH: usize = 32768;
+
+/// Calls `*W` function `query` with a buffer grown until the string it copies fits, as the
+/// psapi name functions cut off longer strings silently.
+///
+/// Returns `None` if `query` fails, returning 0.
+fn query_wide_string(mut query: impl FnMut(*mut u16, DWORD) -> DWORD) -> Option<Vec<u16>> {
+    let mut buffer = vec![0u16; MAX_PATH];
+
+    loop {
+        let length = (query(buffer.as_mut_ptr(), buffer.len() as DWORD) as usize).min(buffer.len());
+
+        if length == 0 {
+            return None;
+        }
+
+        // Truncated strings fill the buffer, except for the null char some functions keep.
+        if length < buffer.len() - 1 || buffer.len() >= MAX_LONG_PATH {
+            buffer.truncate(length);
+            return Some(buffer);
+        }
+
+        buffer.resize(buffer.len() * 2, 0);
+    }
+}
+
+/// Process opened through a handle, which is closed when the process is dropped.
+///
+/// The process can't be cloned, since copies would close the same handle; `try_clone` opens a
+/// second one with a duplicated handle instead.
 #[derive(Debug)]
 pub struct WindowsProcess {
     pid: u32,
     handle: *mut c_void,
+    /// Rights the handle was opened with.
+    access: DWORD,
+    max_read_size: usize,
+    endianness: Endianness,
+    regions: RegionCache,
+    rate_limiter: Option<RateLimiter>,
+    /// Pointer size of the architecture the process runs, queried on first use.
+    pointer_size: OnceLock<usize>,
+    #[cfg(feature = "metrics")]
+    read_counters: ReadCounters,
 }
 
-impl Process for WindowsProcess {
-    fn base_address(&self, module_name: &str) -> Option<usize> {
+// Process handles can be used from any thread, and `ReadProcessMemory` and the other calls made
+// through them are thread-safe. The handle is only closed by `close` and `Drop`, which take the
+// process by value or exclusively.
+unsafe impl Send for WindowsProcess {}
+unsafe impl Sync for WindowsProcess {}
+
+impl PartialEq for WindowsProcess {
+    fn eq(&self, other: &Self) -> bool {
+        self.pid == other.pid
+    }
+}
+
+impl Eq for WindowsProcess {}
+
+impl Hash for WindowsProcess {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pid.hash(state);
+    }
+}
+
+impl WindowsProcess {
+    /// Id of the process.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// File name of the executable, such as `notepad.exe`.
+    pub fn name(&self) -> Option<String> {
+        Some(self.exe_path()?.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Full path of the executable.
+    pub fn exe_path(&self) -> Option<PathBuf> {
+        query_image_path(self.handle)
+    }
+
+    /// Access granted by the rights the handle was opened with.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: self.access & PROCESS_VM_READ != 0,
+            write: self.access & PROCESS_VM_WRITE != 0,
+            operation: self.access & PROCESS_VM_OPERATION != 0,
+        }
+    }
+
+    /// Sets the largest amount of bytes a single `read_bytes` call may request.
+    pub fn set_max_read_size(&mut self, max_read_size: usize) {
+        self.max_read_size = max_read_size;
+    }
+
+    /// Limits reads to at most `reads_per_second`, sleeping before a read when needed.
+    ///
+    /// `None` or 0 removes the limit.
+    pub fn set_rate_limit(&mut self, reads_per_second: Option<u32>) {
+        self.rate_limiter = reads_per_second.and_then(RateLimiter::new);
+    }
+
+    /// Reads per second the process is limited to, if any.
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limiter
+            .as_ref()
+            .map(RateLimiter::reads_per_second)
+    }
+
+    /// Sets how long the region list `is_plausible_pointer` checks against is cached.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.regions.set_policy(policy);
+    }
+
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.regions.policy()
+    }
+
+    /// Reloads the region list `is_plausible_pointer` checks against.
+    pub fn refresh_regions(&self) -> Result<(), MemoryReadError> {
+        self.regions.refresh(|| self.memory_regions())
+    }
+
+    /// Counters of the reads made so far.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> ReadStats {
+        self.read_counters.stats()
+    }
+
+    /// Zeroes the read counters.
+    #[cfg(feature = "metrics")]
+    pub fn reset_stats(&self) {
+        self.read_counters.reset();
+    }
+
+    /// Sets the byte order the number readers decode with.
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Overrides the detected size of the pointers `read_ptr` and the pointer chains read.
+    ///
+    /// Fails with `InvalidFormat` unless the size is 4 or 8.
+    pub fn set_pointer_size(&mut self, pointer_size: usize) -> Result<(), MemoryReadError> {
+        check_pointer_size(pointer_size)?;
+        self.pointer_size = OnceLock::from(pointer_size);
+
+        Ok(())
+    }
+
+    /// Closes the process handle, reporting failure instead of ignoring it like `Drop` does.
+    pub fn close(mut self) -> Result<(), MemoryReadError> {
+        self.close_handle()?;
+
+        Ok(())
+    }
+
+    fn close_handle(&mut self) -> Result<(), IoError> {
+        if self.handle.is_null() {
+            return Ok(());
+        }
+
+        let result = unsafe { CloseHandle(self.handle) };
+        self.handle = ptr::null_mut();
+
+        if result != TRUE {
+            return Err(IoError::last_os_error());
+        }
+
+        trace_event!(debug, pid = self.pid, "closed process handle");
+
+        Ok(())
+    }
+
+    /// Opens a second process with its own duplicate of the handle and the same options.
+    ///
+    /// Read statistics start from zero in the copy.
+    pub fn try_clone(&self) -> Result<WindowsProcess, MemoryReadError> {
+        let mut handle = ptr::null_mut();
+
+        let result = unsafe {
+            DuplicateHandle(
+                GetCurrentProcess(),
+                self.handle,
+                GetCurrentProcess(),
+                &mut handle,
+                0,
+                FALSE,
+                DUPLICATE_SAME_ACCESS,
+            )
+        };
+
+        if result != TRUE {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let mut regions = RegionCache::default();
+        regions.set_policy(self.cache_policy());
+
+        Ok(WindowsProcess {
+            pid: self.pid,
+            handle,
+            access: self.access,
+            max_read_size: self.max_read_size,
+            endianness: self.endianness,
+            regions,
+            rate_limiter: self.rate_limit().and_then(RateLimiter::new),
+            pointer_size: self.pointer_size.clone(),
+            #[cfg(feature = "metrics")]
+            read_counters: ReadCounters::default(),
+        })
+    }
+
+    /// Duplicates this process handle into `target` with `access` rights and returns the remote
+    /// handle value.
+    ///
+    /// `target` must have been opened with `PROCESS_DUP_HANDLE` access.
+    pub fn duplicate_handle_into(
+        &self,
+        target: &WindowsProcess,
+        access: DWORD,
+    ) -> Result<usize, MemoryReadError> {
+        let mut duplicate = ptr::null_mut();
+
+        let result = unsafe {
+            DuplicateHandle(
+                GetCurrentProcess(),
+                self.handle,
+                target.handle,
+                &mut duplicate,
+                access,
+                FALSE,
+                0,
+            )
+        };
+
+        if result != TRUE {
+            return Err(IoError::last_os_error().into());
+        }
+
+        Ok(duplicate as usize)
+    }
+
+    /// Instruction set the process runs, as reported by `IsWow64Process2`, or by
+    /// `IsWow64Process` on Windows versions lacking it.
+    pub fn architecture(&self) -> Option<Architecture> {
+        let machine = match is_wow64_process2() {
+            Some(is_wow64_process2) => {
+                let mut process_machine = 0;
+                let mut native_machine = 0;
+
+                let result = unsafe {
+                    is_wow64_process2(self.handle, &mut process_machine, &mut native_machine)
+                };
+
+                if result != TRUE {
+                    return None;
+                }
+
+                // Processes not running under WOW64 report an unknown process machine.
+                match process_machine {
+                    IMAGE_FILE_MACHINE_UNKNOWN => native_machine,
+                    machine => machine,
+                }
+            }
+            None => self.wow64_machine()?,
+        };
+
+        match machine {
+            IMAGE_FILE_MACHINE_I386 => Some(Architecture::X86),
+            IMAGE_FILE_MACHINE_AMD64 => Some(Architecture::X86_64),
+            IMAGE_FILE_MACHINE_ARM | IMAGE_FILE_MACHINE_ARMNT => Some(Architecture::Arm),
+            IMAGE_FILE_MACHINE_ARM64 => Some(Architecture::Aarch64),
+            _ => None,
+        }
+    }
+
+    /// Machine of the process told by `IsWow64Process`, as WOW64 of those Windows versions only
+    /// runs x86 processes.
+    fn wow64_machine(&self) -> Option<u16> {
+        let mut wow64 = FALSE;
+
+        if unsafe { IsWow64Process(self.handle, &mut wow64) } != TRUE {
+            return None;
+        }
+
+        if wow64 == TRUE {
+            return Some(IMAGE_FILE_MACHINE_I386);
+        }
+
+        let mut info = MaybeUninit::<SYSTEM_INFO>::uninit();
+        unsafe { GetNativeSystemInfo(info.as_mut_ptr()) };
+
+        match unsafe { info.assume_init().u.s().wProcessorArchitecture } {
+            PROCESSOR_ARCHITECTURE_INTEL => Some(IMAGE_FILE_MACHINE_I386),
+            PROCESSOR_ARCHITECTURE_AMD64 => Some(IMAGE_FILE_MACHINE_AMD64),
+            PROCESSOR_ARCHITECTURE_ARM => Some(IMAGE_FILE_MACHINE_ARMNT),
+            _ => None,
+        }
+    }
+
+    /// Reads the stack of thread `tid` between `StackLimit` and `StackBase` of its TEB.
+    ///
+    /// Threads of WOW64 targets run on the stack of their 32-bit TEB, which follows the native
+    /// one.
+    pub fn read_thread_stack(&self, tid: u32) -> Result<Vec<u8>, MemoryReadError> {
+        let teb = self.teb_base(tid)?;
+
+        // `NT_TIB` starts the TEB with `ExceptionList`, `StackBase` and `StackLimit`.
+        let (stack_base, stack_limit) = if self.pointer_size()? < size_of::<usize>() {
+            let teb_32 = teb + WOW64_TEB_OFFSET;
+
+            (
+                self.read_u32(teb_32 + 4)? as usize,
+                self.read_u32(teb_32 + 8)? as usize,
+            )
+        } else {
+            (
+                self.read_native_ptr(teb + size_of::<usize>())?,
+                self.read_native_ptr(teb + 2 * size_of::<usize>())?,
+            )
+        };
+
+        self.read_bytes_vec(stack_limit, stack_base.saturating_sub(stack_limit))
+    }
+
+    /// Reads TLS slot `slot_index` of thread `tid`, as `TlsGetValue` in that thread would.
+    ///
+    /// The first 64 slots live in `TlsSlots` of the TEB, the rest in `TlsExpansionSlots`, which
+    /// stays unallocated, reading as 0, until the thread uses a high slot. Slots of WOW64 targets
+    /// are read from their 32-bit TEB.
+    pub fn read_tls_slot(&self, tid: u32, slot_index: usize) -> Result<usize, MemoryReadError> {
+        if slot_index >= TLS_MINIMUM_AVAILABLE + TLS_EXPANSION_SLOTS {
+            return Err(MemoryReadError::InvalidLength {
+                length: slot_index,
+                max: TLS_MINIMUM_AVAILABLE + TLS_EXPANSION_SLOTS - 1,
+            });
+        }
+
+        let teb = self.teb_base(tid)?;
+        let wow64 = self.pointer_size()? < size_of::<usize>();
+
+        read_tls_slot_at(self, teb, slot_index, wow64)
+    }
+
+    /// Current memory footprint, as reported by `GetProcessMemoryInfo`.
+    pub fn memory_usage(&self) -> Result<MemoryUsage, MemoryReadError> {
+        let counters = self.memory_counters()?;
+
+        Ok(MemoryUsage {
+            working_set: counters.WorkingSetSize,
+            peak_working_set: counters.PeakWorkingSetSize,
+            private_bytes: counters.PrivateUsage,
+        })
+    }
+
+    /// Closest equivalent of the Linux `/proc/<pid>/status` fields, read from a toolhelp
+    /// snapshot and `GetProcessMemoryInfo`.
+    ///
+    /// Windows has no process state or user and group ids, so those are `None`.
+    pub fn status(&self) -> Result<ProcessStatus, MemoryReadError> {
+        let counters = self.memory_counters()?;
+        let mut status = None;
+
+        visit_toolhelp_processes(|process| {
+            if process.th32ProcessID == self.pid {
+                status = Some(ProcessStatus {
+                    name: toolhelp_name(process),
+                    state: None,
+                    ppid: process.th32ParentProcessID,
+                    uid: None,
+                    gid: None,
+                    vm_rss: counters.WorkingSetSize,
+                    vm_size: counters.PagefileUsage,
+                    threads: process.cntThreads as usize,
+                });
+            }
+        })?;
+
+        status.ok_or(MemoryReadError::ProcessNotFound { pid: self.pid })
+    }
+
+    fn memory_counters(&self) -> Result<PROCESS_MEMORY_COUNTERS_EX, MemoryReadError> {
+        let mut counters = MaybeUninit::<PROCESS_MEMORY_COUNTERS_EX>::uninit();
+
+        let result = unsafe {
+            GetProcessMemoryInfo(
+                self.handle,
+                counters.as_mut_ptr() as *mut PROCESS_MEMORY_COUNTERS,
+                size_of::<PROCESS_MEMORY_COUNTERS_EX>() as u32,
+            )
+        };
+
+        if result != TRUE {
+            return Err(IoError::last_os_error().into());
+        }
+
+        Ok(unsafe { counters.assume_init() })
+    }
+
+    /// Checks that the opened process is still running.
+    ///
+    /// The open handle keeps the pid from being reused, so it always refers to the same process.
+    pub fn is_alive(&self) -> bool {
+        self.exit_code().is_none()
+    }
+
+    /// Exit code of the terminated process, or `None` while it is still running.
+    ///
+    /// A process that exited with `STILL_ACTIVE` (259) is reported as running.
+    pub fn exit_code(&self) -> Option<u32> {
+        let mut code: DWORD = 0;
+
+        if unsafe { GetExitCodeProcess(self.handle, &mut code) } != TRUE || code == STILL_ACTIVE {
+            return None;
+        }
+
+        Some(code)
+    }
+
+    /// Lists loaded modules in the order they were loaded, with the PE header timestamps.
+    ///
+    /// Modules that can't be queried are skipped, keeping the load order of the rest.
+    pub fn modules_detailed(&self) -> Result<Vec<LoadedModule>, MemoryReadError> {
+        Ok(self
+            .module_handles()?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(load_order, handle)| {
+                let module = self.listed_module(handle)?;
+
+                Some(LoadedModule {
+                    timestamp: pe::timestamp(self, module.base).ok(),
+                    module,
+                    load_order,
+                })
+            })
+            .collect())
+    }
+
+    /// Address of the process environment block.
+    pub fn peb_base(&self) -> Result<usize, MemoryReadError> {
+        let mut info = MaybeUninit::<PROCESS_BASIC_INFORMATION>::uninit();
+
+        let status = unsafe {
+            NtQueryInformationProcess(
+                self.handle,
+                ProcessBasicInformation,
+                info.as_mut_ptr() as *mut _,
+                size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+                ptr::null_mut(),
+            )
+        };
+
+        if status < 0 {
+            return Err(nt_status_error(status));
+        }
+
+        Ok(unsafe { info.assume_init() }.PebBaseAddress as usize)
+    }
+
+    /// Command-line arguments of the process, split the way `CommandLineToArgvW` does.
+    ///
+    /// The command line is read from the process parameters of the environment block.
+    pub fn argv(&self) -> Result<Vec<String>, MemoryReadError> {
+        let parameters =
+            self.read_native_ptr(offset_address(self.peb_base()?, 4 * size_of::<usize>())?)?;
+        let mut units =
+            self.read_unicode_string(offset_address(parameters, native_offset(0x40, 0x70))?)?;
+
+        // An empty command line would be parsed as the path of this executable.
+        if units.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        units.push(0);
+
+        let mut count = 0;
+        let args = unsafe { CommandLineToArgvW(units.as_ptr(), &mut count) };
+
+        if args.is_null() {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let argv = (0..count as usize)
+            .map(|index| unsafe {
+                let arg = *args.add(index);
+                let len = (0..).take_while(|&offset| *arg.add(offset) != 0).count();
+
+                OsString::from_wide(slice::from_raw_parts(arg, len))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+
+        unsafe {
+            LocalFree(args as *mut c_void);
+        }
+
+        Ok(argv)
+    }
+
+    /// Full path of the module read from its `LDR_DATA_TABLE_ENTRY` in the loader list of the
+    /// environment block, without asking PSAPI or toolhelp.
+    ///
+    /// The module name is compared ignoring case.
+    pub fn module_path_from_ldr(&self, module_name: &str) -> Result<String, MemoryReadError> {
+        let module_name: Vec<u16> = module_name.encode_utf16().collect();
+        let loader_data =
+            self.read_native_ptr(offset_address(self.peb_base()?, 3 * size_of::<usize>())?)?;
+        let list_head = offset_address(loader_data, native_offset(0x0c, 0x10))?;
+        let mut entry = self.read_native_ptr(list_head)?;
+
+        for _ in 0..MAX_LOADER_ENTRIES {
+            if entry == list_head {
+                break;
+            }
+
+            let base_name =
+                self.read_unicode_string(offset_address(entry, native_offset(0x2c, 0x58))?)?;
+
+            if equals_ignore_case(&base_name, &module_name) {
+                let full_name =
+                    self.read_unicode_string(offset_address(entry, native_offset(0x24, 0x48))?)?;
+
+                return Ok(String::from_utf16_lossy(&full_name));
+            }
+
+            entry = self.read_native_ptr(entry)?;
+        }
+
+        Err(MemoryReadError::ModuleNotFound {
+            name: String::from_utf16_lossy(&module_name),
+        })
+    }
+
+    /// Address of the thread environment block of thread `tid`.
+    pub fn teb_base(&self, tid: u32) -> Result<usize, MemoryReadError> {
+        let thread = unsafe { OpenThread(THREAD_QUERY_LIMITED_INFORMATION, 0, tid) };
+
+        if thread.is_null() {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let mut info = MaybeUninit::<THREAD_BASIC_INFORMATION>::uninit();
+
+        let status = unsafe {
+            NtQueryInformationThread(
+                thread,
+                ThreadBasicInformation,
+                info.as_mut_ptr() as *mut _,
+                size_of::<THREAD_BASIC_INFORMATION>() as u32,
+                ptr::null_mut(),
+            )
+        };
+
+        unsafe {
+            CloseHandle(thread);
+        }
+
+        if status < 0 {
+            return Err(nt_status_error(status));
+        }
+
+        Ok(unsafe { info.assume_init() }.TebBaseAddress as usize)
+    }
+
+    /// Address thread `tid` was started at, as passed to `CreateThread`.
+    pub fn thread_start_address(&self, tid: u32) -> Result<usize, MemoryReadError> {
+        let thread = unsafe { OpenThread(THREAD_QUERY_INFORMATION, 0, tid) };
+
+        if thread.is_null() {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let mut start_address = 0usize;
+
+        let status = unsafe {
+            NtQueryInformationThread(
+                thread,
+                ThreadQuerySetWin32StartAddress,
+                &mut start_address as *mut usize as *mut _,
+                size_of::<usize>() as u32,
+                ptr::null_mut(),
+            )
+        };
+
+        unsafe {
+            CloseHandle(thread);
+        }
+
+        if status < 0 {
+            return Err(nt_status_error(status));
+        }
+
+        Ok(start_address)
+    }
+
+    /// Lists ids of the threads of the process.
+    pub fn thread_ids(&self) -> Result<Vec<u32>, MemoryReadError> {
+        let handle = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) };
+
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(IoError::last_os_error().into());
+        }
+
+        let mut entry = MaybeUninit::<THREADENTRY32>::zeroed();
+        let mut tids = Vec::new();
+
+        unsafe {
+            (*entry.as_mut_ptr()).dwSize = size_of::<THREADENTRY32>() as u32;
+        }
+
+        let mut result = unsafe { Thread32First(handle, entry.as_mut_ptr()) };
+
+        // The snapshot always lists threads of every process.
+        while result == TRUE {
+            let thread = unsafe { entry.assume_init_ref() };
+
+            if thread.th32OwnerProcessID == self.pid {
+                tids.push(thread.th32ThreadID);
+            }
+
+            result = unsafe { Thread32Next(handle, entry.as_mut_ptr()) };
+        }
+
+        unsafe {
+            CloseHandle(handle);
+        }
+
+        Ok(tids)
+    }
+
+    /// Handle of the first module, which is the process executable.
+    fn main_module_handle(&self) -> Option<HMODULE> {
         let mut maybe_hmod = MaybeUninit::<HMODULE>::uninit();
         let mut maybe_cb_needed = MaybeUninit::<DWORD>::uninit();
 
@@ -93,30 +1073,188 @@ impl Process for WindowsProcess {
             return None;
         }
 
-        let mut base_name_vec: Vec<u8> = Vec::with_capacity(MAX_PATH);
+        unsafe { Some(maybe_hmod.assume_init()) }
+    }
 
-        unsafe {
-            let base_name_length = GetModuleBaseNameA(
+    /// Reads pointer of this process' width, which environment block structures use, also for
+    /// WOW64 targets.
+    fn read_native_ptr(&self, address: usize) -> Result<usize, MemoryReadError> {
+        match size_of::<usize>() {
+            8 => self.read_u64(address).map(|value| value as usize),
+            _ => self.read_u32(address).map(|value| value as usize),
+        }
+    }
+
+    /// Reads characters of the `UNICODE_STRING` at the address.
+    fn read_unicode_string(&self, address: usize) -> Result<Vec<u16>, MemoryReadError> {
+        let mut length = [0u16];
+        self.read_into_slice(address, &mut length)?;
+
+        let mut units = vec![0u16; length[0] as usize / 2];
+
+        if !units.is_empty() {
+            let buffer = self.read_native_ptr(offset_address(address, size_of::<usize>())?)?;
+            self.read_into_slice(buffer, &mut units)?;
+        }
+
+        Ok(units)
+    }
+
+    /// Finds the first loaded module whose base name matches, ignoring case.
+    ///
+    /// Modules whose name can't be queried, such as ones unloaded meanwhile, are skipped.
+    fn find_base_address(&self, module_name: &str) -> Option<usize> {
+        let module_name: Vec<u16> = module_name.encode_utf16().collect();
+
+        self.module_handles()
+            .ok()?
+            .into_iter()
+            .find(|&hmod| {
+                // Failing calls must not compare as an empty name.
+                match self.module_base_name(hmod) {
+                    Some(base_name) => equals_ignore_case(&base_name, &module_name),
+                    None => {
+                        trace_event!(
+                            debug,
+                            pid = self.pid,
+                            error = %IoError::last_os_error(),
+                            "module base name unavailable"
+                        );
+
+                        false
+                    }
+                }
+            })
+            .map(|hmod| hmod as usize)
+    }
+
+    fn module_handles(&self) -> Result<Vec<HMODULE>, IoError> {
+        let mut handles: Vec<HMODULE> = vec![ptr::null_mut(); 256];
+
+        loop {
+            let mut needed: DWORD = 0;
+
+            let result = unsafe {
+                EnumProcessModulesEx(
+                    self.handle,
+                    handles.as_mut_ptr(),
+                    size_of_val(handles.as_slice()) as u32,
+                    &mut needed,
+                    LIST_MODULES_ALL,
+                )
+            };
+
+            if result != TRUE {
+                return Err(IoError::last_os_error());
+            }
+
+            let count = needed as usize / size_of::<HMODULE>();
+
+            if count <= handles.len() {
+                handles.truncate(count);
+                return Ok(handles);
+            }
+
+            handles.resize(count, ptr::null_mut());
+        }
+    }
+
+    /// Device path (`\Device\HarddiskVolume1\...`) of the file mapped at the address.
+    fn mapped_file_name(&self, address: usize) -> Option<PathBuf> {
+        let path = query_wide_string(|buffer, size| unsafe {
+            GetMappedFileNameW(self.handle, address as *mut _, buffer, size)
+        })?;
+
+        Some(OsString::from_wide(&path).into())
+    }
+
+    fn module_base_name(&self, handle: HMODULE) -> Option<Vec<u16>> {
+        query_wide_string(|buffer, size| unsafe {
+            GetModuleBaseNameW(self.handle, handle, buffer, size)
+        })
+    }
+
+    fn module(&self, handle: HMODULE) -> Result<Module, IoError> {
+        let name = self
+            .module_base_name(handle)
+            .ok_or_else(IoError::last_os_error)?;
+        let path = query_wide_string(|buffer, size| unsafe {
+            GetModuleFileNameExW(self.handle, handle, buffer, size)
+        })
+        .unwrap_or_default();
+        let mut info = MaybeUninit::<MODULEINFO>::uninit();
+
+        let result = unsafe {
+            GetModuleInformation(
                 self.handle,
-                maybe_hmod.assume_init(),
-                base_name_vec.as_mut_ptr() as *mut _,
-                base_name_vec.capacity() as u32,
-            );
+                handle,
+                info.as_mut_ptr(),
+                size_of::<MODULEINFO>() as u32,
+            )
+        };
 
-            base_name_vec.set_len(base_name_length as usize)
+        if result != TRUE {
+            return Err(IoError::last_os_error());
         }
 
-        let base_name = String::from_utf8_lossy(&base_name_vec);
+        let info = unsafe { info.assume_init() };
 
-        if base_name.to_lowercase() == module_name.to_lowercase() {
-            unsafe { Some(maybe_hmod.assume_init() as usize) }
-        } else {
-            None
+        Ok(Module {
+            name: String::from_utf16_lossy(&name),
+            path: PathBuf::from(OsString::from_wide(&path)),
+            base: info.lpBaseOfDll as usize,
+            size: info.SizeOfImage as usize,
+        })
+    }
+
+    /// Same as `module`, skipping modules that can't be queried, such as ones unloaded since
+    /// they were listed, instead of failing the whole list.
+    fn listed_module(&self, handle: HMODULE) -> Option<Module> {
+        let module = self.module(handle);
+
+        if module.is_err() {
+            trace_event!(
+                debug,
+                pid = self.pid,
+                error = ?module.as_ref().err(),
+                "skipped module that could not be queried"
+            );
         }
+
+        module.ok()
     }
 
-    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
-        let mut maybe_read = MaybeUninit::<usize>::uninit();
+    fn read_process_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        // Empty reads are answered without asking the OS, which handles them inconsistently.
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait();
+        }
+
+        let result = self.read_memory(address, buffer);
+
+        #[cfg(feature = "metrics")]
+        self.read_counters.record(&result);
+
+        result
+    }
+
+    fn read_memory(
+        &self,
+        address: usize,
+        buffer: &mut [MaybeUninit<u8>],
+    ) -> Result<usize, MemoryReadError> {
+        check_read_size(buffer.len(), self.max_read_size)?;
+        offset_address(address, buffer.len())?;
+
+        let mut read = 0;
 
         let result = unsafe {
             ReadProcessMemory(
@@ -124,15 +1262,216 @@ impl Process for WindowsProcess {
                 address as *const _,
                 buffer.as_mut_ptr() as *mut _,
                 buffer.len(),
-                maybe_read.as_mut_ptr(),
+                &mut read,
+            )
+        };
+
+        if result != TRUE {
+            let os_error = IoError::last_os_error();
+
+            // Reads ending in an inaccessible page fail, but still copy the accessible part.
+            if os_error.raw_os_error() == Some(ERROR_PARTIAL_COPY as i32) && read > 0 {
+                return Ok(read);
+            }
+
+            return Err(MemoryReadError::InaccessibleMemoryAddress {
+                address,
+                os_error: Some(os_error),
+            });
+        }
+
+        Ok(read)
+    }
+}
+
+impl ProcessWriter for WindowsProcess {
+    fn write_bytes(&self, address: usize, bytes: &[u8]) -> Result<(), MemoryWriteError> {
+        let missing = self
+            .capabilities()
+            .missing(&[Capability::Write, Capability::Operation]);
+
+        if let Some(needed) = missing {
+            return Err(MemoryWriteError::MissingCapability { needed });
+        }
+
+        let mut written = 0;
+
+        let result = unsafe {
+            WriteProcessMemory(
+                self.handle,
+                address as *mut _,
+                bytes.as_ptr() as *const _,
+                bytes.len(),
+                &mut written,
             )
         };
+        trace_event!(
+            trace,
+            pid = self.pid,
+            address,
+            len = bytes.len(),
+            "write_bytes"
+        );
 
         if result != TRUE {
-            return Err(MemoryReadError::InaccessibleMemoryAddress { address });
+            let os_error = IoError::last_os_error();
+
+            if os_error.raw_os_error() == Some(ERROR_PARTIAL_COPY as i32) && written > 0 {
+                return Err(MemoryWriteError::LessBytesWritten {
+                    expected: bytes.len(),
+                    actual: written,
+                });
+            }
+
+            return Err(MemoryWriteError::InaccessibleMemoryAddress {
+                address,
+                os_error: Some(os_error),
+            });
         }
 
-        let read = unsafe { maybe_read.assume_init() };
+        Ok(())
+    }
+}
+
+impl Process for WindowsProcess {
+    fn base_address(&self, module_name: &str) -> Option<usize> {
+        let base = self.find_base_address(module_name);
+        trace_event!(debug, pid = self.pid, module_name, base = ?base, "resolved module base address");
+
+        base
+    }
+
+    fn main_module_base(&self) -> Option<usize> {
+        self.main_module_handle().map(|hmod| hmod as usize)
+    }
+
+    fn memory_regions(&self) -> Result<Vec<MemoryRegion>, MemoryReadError> {
+        let mut regions = Vec::new();
+        let mut address = 0usize;
+
+        loop {
+            let mut info = MaybeUninit::<MEMORY_BASIC_INFORMATION>::uninit();
+
+            let written = unsafe {
+                VirtualQueryEx(
+                    self.handle,
+                    address as *const _,
+                    info.as_mut_ptr(),
+                    size_of::<MEMORY_BASIC_INFORMATION>(),
+                )
+            };
+
+            if written == 0 {
+                let error = IoError::last_os_error();
+
+                // Querying past the highest user-mode address ends the walk.
+                if error.raw_os_error() == Some(ERROR_INVALID_PARAMETER as i32) {
+                    break;
+                }
+
+                return Err(error.into());
+            }
+
+            let info = unsafe { info.assume_init() };
+            let base = info.BaseAddress as usize;
+
+            if info.State == MEM_COMMIT {
+                let kind = match info.Type {
+                    MEM_IMAGE | MEM_MAPPED => self
+                        .mapped_file_name(base)
+                        .map_or(RegionKind::Anonymous, RegionKind::File),
+                    _ => RegionKind::Anonymous,
+                };
+
+                regions.push(region_from_protection(
+                    base,
+                    info.RegionSize,
+                    info.Protect,
+                    kind,
+                ));
+            }
+
+            address = match base.checked_add(info.RegionSize) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        Ok(regions)
+    }
+
+    /// Modules that can't be queried, such as ones unloaded since they were listed, are skipped.
+    fn modules(&self) -> Result<Vec<Module>, MemoryReadError> {
+        let mut modules = self
+            .module_handles()?
+            .into_iter()
+            .filter_map(|handle| self.listed_module(handle))
+            .collect::<Vec<_>>();
+
+        modules.sort_by_key(|module| module.base);
+
+        Ok(modules)
+    }
+
+    fn is_plausible_pointer(&self, value: usize) -> bool {
+        is_aligned_pointer(self, value) && self.regions.is_readable(value, || self.memory_regions())
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn max_read_size(&self) -> usize {
+        self.max_read_size
+    }
+
+    /// Pointer size of the architecture the process runs, so 4 for WOW64 processes, or of this
+    /// process when it can't be queried, unless overridden with `set_pointer_size`.
+    fn pointer_size(&self) -> Result<usize, MemoryReadError> {
+        Ok(*self.pointer_size.get_or_init(|| {
+            self.architecture()
+                .map_or(size_of::<usize>(), Architecture::pointer_size)
+        }))
+    }
+
+    fn page_size(&self) -> usize {
+        let mut info = MaybeUninit::<SYSTEM_INFO>::uninit();
+        unsafe { GetSystemInfo(info.as_mut_ptr()) };
+
+        unsafe { info.assume_init() }.dwPageSize as usize
+    }
+
+    fn read_bytes(&self, address: usize, buffer: &mut [u8]) -> Result<(), MemoryReadError> {
+        let result = self
+            .read_process_memory(address, as_uninit_mut(buffer))
+            .and_then(|read| {
+                if read != buffer.len() {
+                    return Err(MemoryReadError::LessBytesRead {
+                        expected: buffer.len(),
+                        actual: read,
+                    });
+                }
+
+                Ok(())
+            });
+        trace_event!(
+            trace,
+            pid = self.pid,
+            address,
+            len = buffer.len(),
+            error = ?result.as_ref().err(),
+            "read_bytes"
+        );
+
+        result
+    }
+
+    fn read_bytes_uninit<'a>(
+        &self,
+        address: usize,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], MemoryReadError> {
+        let read = self.read_process_memory(address, buffer)?;
 
         if read != buffer.len() {
             return Err(MemoryReadError::LessBytesRead {
@@ -141,14 +1480,94 @@ impl Process for WindowsProcess {
             });
         }
 
-        Ok(())
+        Ok(unsafe { assume_init_mut(buffer) })
+    }
+
+    fn read_bytes_partial(
+        &self,
+        address: usize,
+        buffer: &mut [u8],
+    ) -> Result<usize, MemoryReadError> {
+        self.read_process_memory(address, as_uninit_mut(buffer))
     }
 }
 
 impl Drop for WindowsProcess {
     fn drop(&mut self) {
-        unsafe {
-            CloseHandle(self.handle);
-        }
+        let _ = self.close_handle();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_handle(name: &str) -> usize {
+        let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+
+        unsafe { GetModuleHandleW(name.as_ptr()) as usize }
+    }
+
+    #[test]
+    fn resolves_base_address_of_secondary_module() {
+        let process = open_process(std::process::id()).unwrap();
+        let kernel32 = module_handle("kernel32.dll");
+
+        assert_ne!(kernel32, 0);
+        assert_eq!(process.base_address("kernel32.dll"), Some(kernel32));
+        assert_eq!(process.base_address("KERNEL32.DLL"), Some(kernel32));
+        assert_ne!(process.main_module_base(), Some(kernel32));
+    }
+
+    #[test]
+    fn grows_buffer_for_long_strings() {
+        let long: Vec<u16> = "a".repeat(600).encode_utf16().collect();
+        let mut calls = 0;
+
+        // Copies like `GetModuleFileNameExW`, cutting the string off at the buffer size.
+        let string = query_wide_string(|buffer, size| {
+            calls += 1;
+            let length = long.len().min(size as usize);
+            unsafe { ptr::copy_nonoverlapping(long.as_ptr(), buffer, length) };
+
+            length as DWORD
+        });
+
+        assert_eq!(string.unwrap(), long);
+        assert_eq!(calls, 3);
+        assert_eq!(query_wide_string(|_, _| 0), None);
+    }
+
+    #[test]
+    fn reads_tls_slots_of_native_and_wow64_tebs() {
+        const TEB: usize = 0x10000;
+        let mut bytes = vec![0u8; WOW64_TEB_OFFSET + 0x1000];
+        let mut write = |offset: usize, value: &[u8]| {
+            bytes[offset..offset + value.len()].copy_from_slice(value);
+        };
+
+        let native_slot = size_of::<usize>();
+        write(
+            native_offset(0xe10, 0x1480) + 3 * native_slot,
+            &0x1234usize.to_le_bytes(),
+        );
+        write(native_offset(0xf94, 0x1780), &(TEB + 0x100).to_le_bytes());
+        write(0x100 + 2 * native_slot, &0x5678usize.to_le_bytes());
+
+        // The 32-bit TEB keeps its slots at the 32-bit offsets, 4 bytes apart.
+        write(WOW64_TEB_OFFSET + 0xe10 + 3 * 4, &0x9abcu32.to_le_bytes());
+        write(
+            WOW64_TEB_OFFSET + 0xf94,
+            &(TEB as u32 + 0x200).to_le_bytes(),
+        );
+        write(0x200 + 2 * 4, &0xdef0u32.to_le_bytes());
+
+        let process = crate::mock::MockProcess::new().with_region(TEB, bytes);
+
+        assert_eq!(read_tls_slot_at(&process, TEB, 3, false).unwrap(), 0x1234);
+        assert_eq!(read_tls_slot_at(&process, TEB, 66, false).unwrap(), 0x5678);
+        assert_eq!(read_tls_slot_at(&process, TEB, 3, true).unwrap(), 0x9abc);
+        assert_eq!(read_tls_slot_at(&process, TEB, 66, true).unwrap(), 0xdef0);
+        assert_eq!(read_tls_slot_at(&process, TEB, 4, true).unwrap(), 0);
     }
 }